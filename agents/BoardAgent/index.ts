@@ -176,6 +176,25 @@ Keep response under 200 words.
     }
   }
 
+  async closeSales(boardPda: PublicKey): Promise<string> {
+    try {
+      const tx = await this.program.methods
+        .closeSales()
+        .accounts({
+          board: boardPda,
+          closer: this.provider.wallet.publicKey,
+        })
+        .rpc();
+
+      this.emit('salesClosed', { boardPda, signature: tx });
+
+      return tx;
+    } catch (error) {
+      console.error('Error closing sales:', error);
+      throw error;
+    }
+  }
+
   async getAvailableSquares(boardPda: PublicKey): Promise<number[]> {
     try {
       const boardState = await this.getBoardState(boardPda);
@@ -29,6 +29,7 @@ export class OracleAgent extends EventEmitter {
   private provider: AnchorProvider;
   private program: Program;
   private scoreFeed: PublicKey;
+  private oracleRegistry: PublicKey;
   private monitoringInterval: NodeJS.Timeout | null = null;
 
   constructor(
@@ -41,6 +42,12 @@ export class OracleAgent extends EventEmitter {
     this.provider = provider;
     this.program = program;
 
+    // Only registry reporters (or the host, if the board allows it) may record
+    [this.oracleRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle_registry')],
+      program.programId,
+    );
+
     if (!process.env.OPENAI_API_KEY) {
       throw new Error('OPENAI_API_KEY is required');
     }
@@ -124,10 +131,14 @@ export class OracleAgent extends EventEmitter {
         this.program.programId,
       );
 
+      // The final report closes the game, including any overtime period
+      const isFinal = score.gameStatus === 'finished';
+
       const tx = await this.program.methods
-        .recordScore(score.homeScore, score.awayScore, score.quarter)
+        .recordScore(score.homeScore, score.awayScore, score.quarter, isFinal)
         .accounts({
           board: boardPda,
+          oracleRegistry: this.oracleRegistry,
           authority: this.provider.wallet.publicKey,
         })
         .rpc();
@@ -16,11 +16,29 @@ import pool from '../../lib/db';
 
 dotenv.config();
 
+type GameState =
+  | 'created'
+  | 'salesClosed'
+  | 'randomized'
+  | 'started'
+  | 'ended'
+  | 'settled'
+  | 'cancelled';
+
 interface GameContext {
   gameId: number;
   boardPda: PublicKey;
-  gameState: 'created' | 'randomized' | 'started' | 'ended' | 'settled';
+  gameState: GameState;
+  randomnessMode: 'vrf' | 'commitReveal';
+  headerMode: 'salesFirst' | 'headersFirst';
+  randomnessAccount: PublicKey | null; // Switchboard randomness account, null on localnet
+  salesCloseAt: number;
+  closingSlotHashRecorded: boolean; // sales_closed_slot_hash is set
+  squaresSold: number;
+  squareCount: number;
   currentScore: { home: number; away: number; quarter: number };
+  periodCount: number; // sport_rules.periods, overtime included
+  settledPeriods: number[]; // 1-based periods whose outcome is no longer pending
   totalPot: number;
   playersCount: number;
 }
@@ -62,6 +80,8 @@ export class OrchestratorAgent extends EventEmitter {
   private provider: AnchorProvider;
   private program: Program;
   private activeGames: Map<number, GameContext> = new Map();
+  // Commit-reveal seeds the host committed to, keyed by game ID
+  private revealSeeds: Map<number, number[]> = new Map();
   private taskQueue: Array<any> = [];
   private isProcessing: boolean = false;
 
@@ -109,8 +129,8 @@ Game Context:
 - Players: ${context.playersCount}
 
 Available Agents:
-- BoardAgent: create_board, get_board_state, purchase_square
-- RandomizerAgent: request_randomization, check_vrf_status
+- BoardAgent: create_board, get_board_state, purchase_square, close_sales
+- RandomizerAgent: request_randomization, record_closing_slot_hash, reveal_seed, check_vrf_status
 - OracleAgent: fetch_scores, poll_game_status
 - WinnerAgent: settle_winner, calculate_payout
 - EmailAgent: send_winner_notification, send_game_updates
@@ -160,18 +180,95 @@ Respond with a JSON object containing:
     }
   }
 
+  registerRevealSeed(gameId: number, seed: number[]): void {
+    this.revealSeeds.set(gameId, seed);
+  }
+
+  // Queues whatever fixes the headers: a VRF request bound to the board's
+  // randomness account, or the host's seed reveal on commit-reveal boards
+  private randomizeTasks(
+    context: GameContext,
+    priority: number,
+  ): TaskPlan['tasks'] {
+    const boardPda = context.boardPda.toString();
+
+    if (context.randomnessMode === 'vrf') {
+      const vrfAccount = new RandomizerAgent(
+        this.connection,
+        this.provider,
+        this.program,
+      ).getVrfAccount(context.boardPda, context.randomnessAccount ?? undefined);
+
+      return [
+        {
+          agent: 'RandomizerAgent',
+          action: 'request_randomization',
+          args: { boardPda, vrfAccount: vrfAccount.toString() },
+          priority,
+          dependencies: [],
+        },
+      ];
+    }
+
+    const seed = this.revealSeeds.get(context.gameId);
+    if (!seed) {
+      console.warn(`No reveal seed registered for game ${context.gameId}`);
+      return [];
+    }
+
+    return [
+      {
+        agent: 'RandomizerAgent',
+        action: 'reveal_seed',
+        args: { boardPda, seed },
+        priority,
+        dependencies: [],
+      },
+    ];
+  }
+
   private generateFallbackPlan(context: GameContext): TaskPlan {
     const tasks: TaskPlan['tasks'] = [];
+    const now = Math.floor(Date.now() / 1000);
 
     switch (context.gameState) {
+      // Headers-first boards randomize while open; sales-first boards close
+      // sales once full or past the cutoff, then randomize
       case 'created':
-        tasks.push({
-          agent: 'RandomizerAgent',
-          action: 'request_randomization',
-          args: { boardPda: context.boardPda.toString() },
-          priority: 10,
-          dependencies: [],
-        });
+        if (context.headerMode === 'headersFirst') {
+          tasks.push(...this.randomizeTasks(context, 10));
+        } else if (
+          context.squaresSold === context.squareCount ||
+          now >= context.salesCloseAt
+        ) {
+          tasks.push({
+            agent: 'BoardAgent',
+            action: 'close_sales',
+            args: { boardPda: context.boardPda.toString() },
+            priority: 10,
+            dependencies: [],
+          });
+          // Commit-reveal boards record the closing slot hash on the next
+          // pass, once close_sales has fixed the slot
+          if (context.randomnessMode === 'vrf') {
+            tasks.push(...this.randomizeTasks(context, 9));
+          }
+        }
+        break;
+      case 'salesClosed':
+        if (
+          context.randomnessMode === 'commitReveal' &&
+          !context.closingSlotHashRecorded
+        ) {
+          tasks.push({
+            agent: 'RandomizerAgent',
+            action: 'record_closing_slot_hash',
+            args: { boardPda: context.boardPda.toString() },
+            priority: 10,
+            dependencies: [],
+          });
+        }
+        tasks.push(...this.randomizeTasks(context, 9));
         break;
       case 'randomized':
         tasks.push({
@@ -191,14 +288,22 @@ Respond with a JSON object containing:
           dependencies: [],
         });
         break;
+      // Periods settle strictly in order, and an overtime quarter is not a
+      // period index, so walk every period rather than the last quarter
       case 'ended':
-        tasks.push({
-          agent: 'WinnerAgent',
-          action: 'settle_winner',
-          args: { boardPda: context.boardPda.toString() },
-          priority: 10,
-          dependencies: [],
-        });
+        for (let quarter = 1; quarter <= context.periodCount; quarter++) {
+          if (context.settledPeriods.includes(quarter)) continue;
+          tasks.push({
+            agent: 'WinnerAgent',
+            action: 'settle_winner',
+            args: { boardPda: context.boardPda.toString(), quarter },
+            priority: 10,
+            dependencies: [],
+          });
+        }
+        break;
+      // Cancelled boards are terminal; players reclaim their own refunds
+      case 'cancelled':
         break;
     }
 
@@ -266,6 +371,9 @@ Respond with a JSON object containing:
       case 'get_board_state':
         await boardAgent.getBoardState(task.args.boardPda);
         break;
+      case 'close_sales':
+        await boardAgent.closeSales(new PublicKey(task.args.boardPda));
+        break;
       default:
         console.warn(`Unknown board action: ${task.action}`);
     }
@@ -280,7 +388,21 @@ Respond with a JSON object containing:
 
     switch (task.action) {
       case 'request_randomization':
-        await randomizerAgent.requestRandomization(task.args.boardPda);
+        await randomizerAgent.requestRandomization(
+          new PublicKey(task.args.boardPda),
+          new PublicKey(task.args.vrfAccount),
+        );
+        break;
+      case 'record_closing_slot_hash':
+        await randomizerAgent.recordClosingSlotHash(
+          new PublicKey(task.args.boardPda),
+        );
+        break;
+      case 'reveal_seed':
+        await randomizerAgent.revealSeed(
+          new PublicKey(task.args.boardPda),
+          task.args.seed,
+        );
         break;
       case 'check_vrf_status':
         await randomizerAgent.checkVrfStatus(task.args.vrfAccount);
@@ -318,7 +440,7 @@ Respond with a JSON object containing:
 
     switch (task.action) {
       case 'settle_winner':
-        await winnerAgent.settleWinner(task.args.boardPda);
+        await winnerAgent.settleWinner(task.args.boardPda, task.args.quarter);
         break;
       case 'calculate_payout':
         await winnerAgent.calculatePayout(task.args.boardPda);
@@ -415,11 +537,20 @@ Respond with a JSON object containing:
         gameId,
         boardPda,
         gameState,
+        randomnessMode: 'vrf', // Mock randomness mode
+        headerMode: 'salesFirst', // Mock header mode
+        randomnessAccount: null, // Mock VRF account (localnet)
+        salesCloseAt: 0, // Mock sales cutoff
+        closingSlotHashRecorded: false, // Mock closing slot hash
+        squaresSold: 0, // Mock squares sold
+        squareCount: 100, // Mock grid size
         currentScore: {
           home: 0, // Mock score
           away: 0, // Mock score
           quarter: 1, // Mock quarter
         },
+        periodCount: 4, // Mock sport_rules.periods
+        settledPeriods: [], // Mock settled periods
         totalPot: 0, // Mock total pot
         playersCount: 0, // Mock players count
       };
@@ -431,20 +562,26 @@ Respond with a JSON object containing:
 
   private determineGameState(
     boardAccount: any,
-  ): 'created' | 'randomized' | 'started' | 'ended' | 'settled' {
-    if (!boardAccount.winner.equals(PublicKey.default)) {
-      return 'settled';
-    }
-    if (boardAccount.gameEnded) {
-      return 'ended';
-    }
-    if (boardAccount.gameStarted) {
-      return 'started';
-    }
-    if (boardAccount.randomized) {
-      return 'randomized';
+  ): GameState {
+    // Anchor decodes BoardStatus as a single-key object, e.g. { live: {} }
+    const status = Object.keys(boardAccount.status)[0];
+    switch (status) {
+      case 'settled':
+      case 'paid':
+        return 'settled';
+      case 'final':
+        return 'ended';
+      case 'live':
+        return 'started';
+      case 'randomized':
+        return 'randomized';
+      case 'salesClosed':
+        return 'salesClosed';
+      case 'cancelled':
+        return 'cancelled';
+      default:
+        return 'created';
     }
-    return 'created';
   }
 
   async addGame(gameId: number): Promise<void> {
@@ -21,6 +21,8 @@ export class RandomizerAgent extends EventEmitter {
   private provider: AnchorProvider;
   private program: Program;
   private vrfQueue: PublicKey;
+  private oracleRegistry: PublicKey;
+  private mockVrfProgramId: PublicKey | null;
 
   constructor(
     connection: Connection,
@@ -47,23 +49,55 @@ export class RandomizerAgent extends EventEmitter {
 
     this.vrfQueue = new PublicKey(vrfQueueAddress);
 
+    // The program only accepts VRF requests and results from registry reporters
+    [this.oracleRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle_registry')],
+      program.programId,
+    );
+
+    // Localnet builds read randomness from the mock VRF program instead of Switchboard
+    this.mockVrfProgramId = process.env.MOCK_VRF_PROGRAM_ID
+      ? new PublicKey(process.env.MOCK_VRF_PROGRAM_ID)
+      : null;
+
     console.log('RandomizerAgent initialized with GPT-4 and Switchboard VRF');
   }
 
+  // The VRF account a board's randomness is read from: a Switchboard
+  // randomness account committed on this agent's queue, or the board's mock
+  // VRF PDA on localnet
+  getVrfAccount(boardPda: PublicKey, randomnessAccount?: PublicKey): PublicKey {
+    if (randomnessAccount) {
+      return randomnessAccount;
+    }
+
+    if (!this.mockVrfProgramId) {
+      throw new Error(
+        'A Switchboard randomness account is required outside localnet',
+      );
+    }
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from('vrf'), boardPda.toBuffer()],
+      this.mockVrfProgramId,
+    )[0];
+  }
+
   async requestRandomization(
     boardPda: PublicKey,
+    randomnessAccount?: PublicKey,
   ): Promise<{ vrfAccount: PublicKey; signature: string }> {
     try {
       console.log(`Requesting randomization for board: ${boardPda.toString()}`);
 
-      // Create VRF account (simplified - in real implementation would use Switchboard SDK)
-      const vrfAccount = new PublicKey('11111111111111111111111111111111'); // Placeholder
+      const vrfAccount = this.getVrfAccount(boardPda, randomnessAccount);
 
       const tx = await this.program.methods
         .requestRandomization()
         .accounts({
           board: boardPda,
           vrfAccount: vrfAccount,
+          oracleRegistry: this.oracleRegistry,
           authority: this.provider.wallet.publicKey,
         })
         .rpc();
@@ -138,6 +172,7 @@ export class RandomizerAgent extends EventEmitter {
         .accounts({
           board: boardPda,
           vrfAccount: vrfAccount,
+          oracleRegistry: this.oracleRegistry,
           authority: this.provider.wallet.publicKey,
         })
         .rpc();
@@ -158,6 +193,45 @@ export class RandomizerAgent extends EventEmitter {
     }
   }
 
+  // Commit-reveal boards mix the closing slot's hash into the seed; anyone can
+  // record it, but only while the SlotHashes sysvar still holds that slot
+  async recordClosingSlotHash(boardPda: PublicKey): Promise<string> {
+    try {
+      const tx = await this.program.methods
+        .recordClosingSlotHash()
+        .accounts({ board: boardPda })
+        .rpc();
+
+      this.emit('closingSlotHashRecorded', { boardPda, signature: tx });
+
+      return tx;
+    } catch (error) {
+      console.error('Error recording closing slot hash:', error);
+      throw error;
+    }
+  }
+
+  async revealSeed(boardPda: PublicKey, seed: number[]): Promise<string> {
+    try {
+      console.log(`Revealing seed for board: ${boardPda.toString()}`);
+
+      const tx = await this.program.methods
+        .revealSeed(seed)
+        .accounts({
+          board: boardPda,
+          authority: this.provider.wallet.publicKey,
+        })
+        .rpc();
+
+      this.emit('seedRevealed', { boardPda, signature: tx });
+
+      return tx;
+    } catch (error) {
+      console.error('Error revealing seed:', error);
+      throw error;
+    }
+  }
+
   async analyzeRandomness(randomness: number[]): Promise<string> {
     const prompt = `
 Analyze this randomness data for a Football Squares board:
@@ -7,6 +7,13 @@ import * as dotenv from 'dotenv';
 
 dotenv.config();
 
+const TOKEN_2022_PROGRAM_ID = new PublicKey(
+  'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
+);
+const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+  'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL',
+);
+
 interface WinnerInfo {
   winner: PublicKey;
   squareIndex: number;
@@ -29,6 +36,7 @@ export class WinnerAgent extends EventEmitter {
   private connection: Connection;
   private provider: AnchorProvider;
   private program: Program;
+  private oracleRegistry: PublicKey;
 
   constructor(
     connection: Connection,
@@ -40,6 +48,12 @@ export class WinnerAgent extends EventEmitter {
     this.provider = provider;
     this.program = program;
 
+    // Only registry reporters (or the host, if the board allows it) may settle
+    [this.oracleRegistry] = PublicKey.findProgramAddressSync(
+      [Buffer.from('oracle_registry')],
+      program.programId,
+    );
+
     if (!process.env.OPENAI_API_KEY) {
       throw new Error('OPENAI_API_KEY is required');
     }
@@ -51,11 +65,36 @@ export class WinnerAgent extends EventEmitter {
     console.log('WinnerAgent initialized with GPT-4');
   }
 
+  // Every sold square is a Token-2022 NFT held in its owner's associated
+  // token account
+  private squareTokenAccount(
+    boardPda: PublicKey,
+    owner: PublicKey,
+    squareIndex: number,
+  ): PublicKey {
+    const [mint] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('square_mint'),
+        boardPda.toBuffer(),
+        Buffer.from([squareIndex]),
+      ],
+      this.program.programId,
+    );
+
+    return PublicKey.findProgramAddressSync(
+      [owner.toBuffer(), TOKEN_2022_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+      ASSOCIATED_TOKEN_PROGRAM_ID,
+    )[0];
+  }
+
   async settleWinner(
     boardPda: PublicKey,
+    quarter: number,
   ): Promise<{ winnerInfo: WinnerInfo; signature: string }> {
     try {
-      console.log(`Settling winner for board: ${boardPda.toString()}`);
+      console.log(
+        `Settling period ${quarter} winner for board: ${boardPda.toString()}`,
+      );
 
       // First, get the current board state
       // TODO: Replace with actual program account fetch when smart contract is deployed
@@ -75,9 +114,10 @@ export class WinnerAgent extends EventEmitter {
 
       // Execute settle winner instruction
       const tx = await this.program.methods
-        .settleWinner()
+        .settleWinner(quarter)
         .accounts({
           board: boardPda,
+          oracleRegistry: this.oracleRegistry,
           authority: this.provider.wallet.publicKey,
         })
         .rpc();
@@ -113,6 +153,8 @@ export class WinnerAgent extends EventEmitter {
   async payoutWinner(
     boardPda: PublicKey,
     winnerKey: PublicKey,
+    quarter: number,
+    squareIndex: number,
   ): Promise<PayoutResult> {
     try {
       console.log(`Processing payout for winner: ${winnerKey.toString()}`);
@@ -139,12 +181,18 @@ export class WinnerAgent extends EventEmitter {
         throw new Error('No payout amount available');
       }
 
-      // Execute payout instruction
+      // Execute payout instruction; the winner signs and must still hold the
+      // winning square's token
       const tx = await this.program.methods
-        .payoutWinner()
+        .payoutWinner(quarter)
         .accounts({
           board: boardPda,
           winner: winnerKey,
+          winnerTokenAccount: this.squareTokenAccount(
+            boardPda,
+            winnerKey,
+            squareIndex,
+          ),
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -0,0 +1,141 @@
+// Anchor 0.31's generated IDL instructions still call the deprecated AccountInfo::realloc.
+#![allow(deprecated)]
+
+use anchor_lang::prelude::*;
+
+declare_id!("BqhtQoyxKHWRXrfHudbso8j6qSdaYq98QMc1HiW9ajX8");
+
+/// Minimal VRF oracle for local validators and tests.
+///
+/// It exposes the same request/fulfill lifecycle the squares program expects
+/// from a real oracle, but the oracle key simply posts the result without a
+/// proof. Never deploy it anywhere real money is at stake.
+#[program]
+pub mod mock_vrf {
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+        let vrf = &mut ctx.accounts.vrf;
+        vrf.consumer = ctx.accounts.consumer.key();
+        vrf.oracle = ctx.accounts.oracle.key();
+        vrf.request_slot = 0;
+        vrf.fulfilled_slot = 0;
+        vrf.result = [0; 32];
+        vrf.bump = ctx.bumps.vrf;
+
+        msg!("Mock VRF initialized for consumer {}", vrf.consumer);
+        Ok(())
+    }
+
+    pub fn request_randomness(ctx: Context<RequestRandomness>) -> Result<()> {
+        let vrf = &mut ctx.accounts.vrf;
+        let clock = Clock::get()?;
+
+        vrf.request_slot = clock.slot;
+        vrf.fulfilled_slot = 0;
+        vrf.result = [0; 32];
+
+        emit!(RandomnessRequested {
+            vrf: vrf.key(),
+            consumer: vrf.consumer,
+            request_slot: vrf.request_slot,
+        });
+
+        msg!("Randomness requested at slot {}", vrf.request_slot);
+        Ok(())
+    }
+
+    pub fn fulfill_randomness(ctx: Context<FulfillRandomness>, result: [u8; 32]) -> Result<()> {
+        let vrf = &mut ctx.accounts.vrf;
+        let clock = Clock::get()?;
+
+        require!(vrf.request_slot > 0, MockVrfError::NotRequested);
+        require!(vrf.fulfilled_slot == 0, MockVrfError::AlreadyFulfilled);
+
+        vrf.result = result;
+        vrf.fulfilled_slot = clock.slot;
+
+        emit!(RandomnessFulfilled {
+            vrf: vrf.key(),
+            consumer: vrf.consumer,
+            fulfilled_slot: vrf.fulfilled_slot,
+            result,
+        });
+
+        msg!("Randomness fulfilled at slot {}", vrf.fulfilled_slot);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + 32 + 32 + 8 + 8 + 32 + 1,
+        seeds = [b"vrf", consumer.key().as_ref()],
+        bump
+    )]
+    pub vrf: Account<'info, VrfState>,
+    /// CHECK: Only used as a seed; the consumer program validates the binding.
+    pub consumer: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    #[account(
+        mut,
+        seeds = [b"vrf", vrf.consumer.as_ref()],
+        bump = vrf.bump
+    )]
+    pub vrf: Account<'info, VrfState>,
+    pub requester: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    #[account(
+        mut,
+        seeds = [b"vrf", vrf.consumer.as_ref()],
+        bump = vrf.bump,
+        has_one = oracle
+    )]
+    pub vrf: Account<'info, VrfState>,
+    pub oracle: Signer<'info>,
+}
+
+#[account]
+pub struct VrfState {
+    pub consumer: Pubkey,       // Account the randomness is bound to (e.g. a squares board)
+    pub oracle: Pubkey,         // Key allowed to post results
+    pub request_slot: u64,      // Slot of the latest request, 0 if never requested
+    pub fulfilled_slot: u64,    // Slot the result was posted, 0 while pending
+    pub result: [u8; 32],
+    pub bump: u8,
+}
+
+#[event]
+pub struct RandomnessRequested {
+    pub vrf: Pubkey,
+    pub consumer: Pubkey,
+    pub request_slot: u64,
+}
+
+#[event]
+pub struct RandomnessFulfilled {
+    pub vrf: Pubkey,
+    pub consumer: Pubkey,
+    pub fulfilled_slot: u64,
+    pub result: [u8; 32],
+}
+
+#[error_code]
+pub enum MockVrfError {
+    #[msg("Randomness has not been requested")]
+    NotRequested,
+    #[msg("Randomness has already been fulfilled")]
+    AlreadyFulfilled,
+}
@@ -1,8 +1,34 @@
+// Anchor 0.31's generated IDL instructions still call the deprecated AccountInfo::realloc.
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_2022::{self, FreezeAccount, InitializeMint2, MintTo, Token2022};
+use anchor_spl::token_interface::{self, Approve, Mint, Revoke, TokenAccount, TransferChecked};
+#[cfg(feature = "localnet")]
+use mock_vrf::VrfState;
+#[cfg(not(feature = "localnet"))]
+use switchboard_on_demand::{RandomnessAccountData, SWITCHBOARD_ON_DEMAND_PROGRAM_ID};
 
 declare_id!("Fg6PaFprPjfrgxLbfXyAyzsK1m1S82mC2f43s5D2qQq");
 
+pub const MIN_PRICE_PER_SQUARE: u64 = 1_000_000;       // 0.001 SOL
+pub const MAX_PRICE_PER_SQUARE: u64 = 100_000_000_000; // 100 SOL
+
+// Most scoring periods a board can pay out; SportRules picks how many a game has
+pub const PERIODS: usize = 4;
+pub const MAX_OVERTIME_PERIODS: u8 = 10;               // Reported as the periods after regulation
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+pub const MAX_SCORE_REPORTERS: usize = 16;
+pub const MAX_ATTESTATION_AGE_SECS: i64 = 300;
+pub const MAX_CO_OWNERS: usize = 8;
+pub const SLOT_HASHES_LEN: u64 = 512;                   // Newest slots the SlotHashes sysvar keeps
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
 pub enum BoardVisibility {
     Public,      // Anyone can find & join
@@ -10,6 +36,215 @@ pub enum BoardVisibility {
     VipOnly,     // Contract enforces VIP status
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RandomnessMode {
+    // Headers come from the VRF oracle via request_randomization/fulfill_vrf_callback
+    Vrf,
+    // Host commits to sha256(seed) up front and reveals the seed after sales close.
+    // Unrevealed boards become refundable once reveal_deadline passes.
+    CommitReveal {
+        seed_commitment: [u8; 32],
+        reveal_deadline: i64,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum HeaderMode {
+    HeadersFirst, // Legacy: headers are drawn before any square can be bought
+    SalesFirst,   // Squares sell blind; headers are drawn once sales close
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum UnsoldWinnerPolicy {
+    RollForward,      // Carry the share into the next period; the final period falls back to NearestSold
+    NearestSold,      // Pay the sold square closest to the winning cell
+    SplitAmongOwners, // Split equally between every wallet that owns a square
+    RefundProRata,    // Return the share to buyers in proportion to squares owned
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum BoardStatus {
+    Open,             // Squares on sale
+    SalesClosed,      // Sales-first board waiting for its headers
+    Randomized,       // Headers set; headers-first boards keep selling until kickoff
+    Live,             // Scores are being reported
+    Final,            // Final score posted
+    Settled,          // Every period has a result
+    Paid,             // Every payout and claim has gone out
+    Cancelled,
+}
+
+impl BoardStatus {
+    pub fn can_transition_to(self, next: BoardStatus) -> bool {
+        use BoardStatus::*;
+        matches!(
+            (self, next),
+            (Open, SalesClosed)
+                | (Open, Randomized)
+                | (SalesClosed, Randomized)
+                | (Randomized, Live)
+                | (Live, Final)
+                | (Final, Settled)
+                | (Settled, Paid)
+                | (Open | SalesClosed | Randomized | Live | Final, Cancelled)
+        )
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ExpiredHoldPolicy {
+    Refund,           // The deposit goes back to the holder
+    ForfeitToPot,     // The deposit is added to the prize pot
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum OvertimePolicy {
+    NotPlayed,        // The game ends after regulation, even when tied
+    RegulationScore,  // Overtime may be played; the final period pays on the regulation score
+    IncludeOvertime,  // The final period pays on the score after overtime
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ScoringDigit {
+    Last,             // Football, hockey, soccer
+    Tens,             // Basketball, where the last digit is close to random
+}
+
+impl ScoringDigit {
+    pub fn of(self, score: u8) -> u8 {
+        match self {
+            ScoringDigit::Last => score % 10,
+            ScoringDigit::Tens => score / 10 % 10,
+        }
+    }
+}
+
+/// How the board's sport is scored.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct SportRules {
+    pub periods: u8,                // Regulation periods, each paid out; at most PERIODS
+    pub overtime: OvertimePolicy,
+    pub scoring_digit: ScoringDigit,
+    pub max_points_per_period: u8,  // Per team; anything larger is a bad report
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum GridSize {
+    TenByTen,    // 100 squares, one score digit per header
+    FiveByFive,  // 25 squares, two score digits per header
+}
+
+impl GridSize {
+    pub fn side(self) -> u8 {
+        match self {
+            GridSize::TenByTen => 10,
+            GridSize::FiveByFive => 5,
+        }
+    }
+
+    pub fn square_count(self) -> u8 {
+        self.side() * self.side()
+    }
+
+    /// How many score digits each row or column header covers.
+    pub fn digits_per_header(self) -> u8 {
+        10 / self.side()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PeriodType {
+    Regulation,
+    Overtime,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub enum SettlementOutcome {
+    #[default]
+    Pending,
+    Winner,           // Winning square was sold and its owner is paid
+    RolledForward,
+    NearestSold,
+    SplitAmongOwners,
+    RefundProRata,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct CoOwner {
+    pub owner: Pubkey,
+    pub weight: u16,              // Relative share of the square's winnings
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Debug)]
+pub struct PeriodResult {
+    pub split_bps: u16,           // Share of the pot paid for this period
+    pub home_score: u8,
+    pub away_score: u8,
+    pub scored: bool,
+    pub settled: bool,
+    pub winner: Pubkey,
+    pub square_index: u8,
+    pub payout_amount: u64,
+    pub paid: bool,
+    pub outcome: SettlementOutcome,
+    pub rollover_in: u64,         // Lamports rolled forward from the previous period
+    pub claimants: u8,            // Shares payout_amount is divided into for split or co-owned outcomes
+    pub claimed_squares: u128,    // Bitmap of squares (or co-owners, for a shared winner) that have claimed
+}
+
+impl PeriodResult {
+    /// Whether everything owed for this settled period has left the board.
+    pub fn is_paid_out(&self) -> bool {
+        match self.outcome {
+            SettlementOutcome::Pending => false,
+            SettlementOutcome::RolledForward => true,
+            SettlementOutcome::Winner | SettlementOutcome::NearestSold => self.paid || self.payout_amount == 0,
+            // A period with no payout has nothing to claim
+            SettlementOutcome::SplitAmongOwners | SettlementOutcome::RefundProRata => {
+                self.payout_amount == 0 || self.claimed_squares.count_ones() == self.claimants as u32
+            }
+        }
+    }
+}
+
+/// Game rules chosen by the host when the board is created.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct BoardConfig {
+    pub randomness_mode: RandomnessMode,
+    pub header_mode: HeaderMode,
+    pub payout_split_bps: [u16; PERIODS], // Per period, unused periods 0; must sum to 10000
+    pub unsold_winner_policy: UnsoldWinnerPolicy,
+    pub settle_deadline: i64,             // After this anyone may cancel an unsettled board
+    pub sales_open_at: i64,               // First second squares can be bought
+    pub sales_close_at: i64,              // Purchases stop; anyone may then close sales
+    pub kickoff_at: i64,                  // Scheduled start of the game, no earlier than sales_close_at
+    pub max_squares_per_wallet: u8,       // 0 for no limit
+    pub hold_duration_secs: i64,          // How long a square hold lasts; 0 disables holds
+    pub hold_deposit: u64,                // Lamports a buyer stakes to hold a square
+    pub expired_hold_policy: ExpiredHoldPolicy,
+    pub resale_royalty_bps: u16,          // Host's cut of each resale
+    pub host_can_report: bool,            // Let the host act as an oracle alongside registered reporters
+    pub score_quorum: u8,                 // Matching reports needed before a score is official
+    pub dispute_window_secs: i64,         // Challenge period after the final score; 0 disables disputes
+    pub dispute_bond: u64,                // Lamports a square owner stakes to dispute a score
+    pub arbiter: Pubkey,                  // Resolves disputes
+    pub grid_size: GridSize,
+    pub sport_rules: SportRules,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CancelReason {
+    HostCancelled,
+    SettleDeadlinePassed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CorrectionReason {
+    ReportingError,               // The reporter posted the wrong numbers
+    OfficialReview,               // The league changed the result after review
+    StatCorrection,               // A scoring play was re-credited after the period
+}
+
 #[program]
 pub mod squares {
     use super::*;
@@ -18,29 +253,103 @@ pub mod squares {
         ctx: Context<CreateBoard>, 
         game_id: u64, 
         price_per_square: u64, 
-        visibility: BoardVisibility
+        visibility: BoardVisibility,
+        config: BoardConfig
     ) -> Result<()> {
         let board = &mut ctx.accounts.board;
         let clock = Clock::get()?;
+
+        validate_price(price_per_square)?;
+        let rules = config.sport_rules;
+        require!(
+            rules.periods >= 1 && rules.periods as usize <= PERIODS && rules.max_points_per_period > 0,
+            SquaresError::InvalidSportRules
+        );
+        require!(
+            config.payout_split_bps.iter().map(|bps| *bps as u64).sum::<u64>() == BPS_DENOMINATOR
+                && config.payout_split_bps[rules.periods as usize..].iter().all(|bps| *bps == 0),
+            SquaresError::InvalidPayoutSplit
+        );
+        require!(
+            config.score_quorum >= 1 && config.score_quorum as usize <= MAX_SCORE_REPORTERS,
+            SquaresError::InvalidQuorum
+        );
+        require!(config.dispute_window_secs >= 0, SquaresError::InvalidDisputeConfig);
+        if config.dispute_window_secs > 0 {
+            require!(
+                config.dispute_bond > 0 && config.arbiter != Pubkey::default(),
+                SquaresError::InvalidDisputeConfig
+            );
+        }
+        require!(config.settle_deadline > clock.unix_timestamp, SquaresError::InvalidSettleDeadline);
+        require!(
+            (config.resale_royalty_bps as u64) <= BPS_DENOMINATOR,
+            SquaresError::InvalidRoyalty
+        );
+        require!(
+            config.hold_duration_secs >= 0 && (config.hold_duration_secs == 0 || config.hold_deposit > 0),
+            SquaresError::InvalidHoldConfig
+        );
+        require!(
+            config.sales_open_at < config.sales_close_at
+                && config.sales_close_at > clock.unix_timestamp
+                && config.sales_close_at <= config.kickoff_at
+                && config.kickoff_at < config.settle_deadline,
+            SquaresError::InvalidSalesWindow
+        );
+        if let RandomnessMode::CommitReveal { reveal_deadline, .. } = config.randomness_mode {
+            // The host must still be able to reveal after sales close on schedule
+            require!(reveal_deadline > config.sales_close_at, SquaresError::InvalidRevealDeadline);
+            // The buyers' entropy only exists once sales are done
+            require!(config.header_mode == HeaderMode::SalesFirst, SquaresError::WrongHeaderMode);
+        }
         
         board.game_id = game_id;
         board.authority = *ctx.accounts.authority.key;
-        board.finalized = false;
-        board.randomized = false;
-        board.game_started = false;
-        board.game_ended = false;
-        board.winner = Pubkey::default();
-        board.payout_amount = 0;
+        board.status = BoardStatus::Open;
         board.total_pot = 0;
         board.home_score = 0;
         board.away_score = 0;
         board.quarter = 0;
+        board.periods = [PeriodResult::default(); PERIODS];
+        for (period, split_bps) in board.periods.iter_mut().zip(config.payout_split_bps) {
+            period.split_bps = split_bps;
+        }
+        board.unsold_winner_policy = config.unsold_winner_policy;
+        board.settle_deadline = config.settle_deadline;
+        board.sales_open_at = config.sales_open_at;
+        board.sales_close_at = config.sales_close_at;
+        board.kickoff_at = config.kickoff_at;
+        board.max_squares_per_wallet = config.max_squares_per_wallet;
+        board.hold_duration_secs = config.hold_duration_secs;
+        board.hold_deposit = config.hold_deposit;
+        board.expired_hold_policy = config.expired_hold_policy;
+        board.resale_royalty_bps = config.resale_royalty_bps;
+        board.held_squares = 0;
+        board.host_can_report = config.host_can_report;
+        board.score_quorum = config.score_quorum;
+        board.sport_rules = config.sport_rules;
+        board.grid_size = config.grid_size;
+        board.dispute_window_secs = config.dispute_window_secs;
+        board.dispute_bond = config.dispute_bond;
+        board.arbiter = config.arbiter;
+        board.final_scored_at = 0;
+        board.open_disputes = 0;
+        board.last_attestation_at = 0;
         // Initialize all 100 squares to the system program, signifying they are unclaimed.
         board.squares = [Pubkey::default(); 100];
         // Headers will be set later by the randomizer agent. 10 is a sentinel for "not set".
         board.home_headers = [10; 10];
         board.away_headers = [10; 10];
         board.bump = ctx.bumps.board;
+        board.vrf_account = Pubkey::default();
+        board.vrf_requested_slot = 0;
+        board.sales_closed_slot = 0;
+        board.sales_closed_slot_hash = [0; 32];
+        board.randomness_mode = config.randomness_mode;
+        board.header_mode = config.header_mode;
+        board.entropy = [0; 32];
+        board.refunded_squares = 0;
         
         // Initialize Board Boost fields
         board.boost_amount = 0;
@@ -62,14 +371,38 @@ pub mod squares {
 
     pub fn request_randomization(ctx: Context<RequestRandomization>) -> Result<()> {
         let board = &mut ctx.accounts.board;
-        
-        require!(!board.randomized, SquaresError::AlreadyRandomized);
-        require!(!board.game_started, SquaresError::GameAlreadyStarted);
+        let clock = Clock::get()?;
+
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        require!(board.randomness_mode == RandomnessMode::Vrf, SquaresError::WrongRandomnessMode);
+        require!(!board.is_randomized(), SquaresError::AlreadyRandomized);
+        // Sales-first boards keep their headers hidden until nobody can buy
+        require!(
+            board.header_mode == HeaderMode::HeadersFirst || board.status == BoardStatus::SalesClosed,
+            SquaresError::SalesStillOpen
+        );
+        // A second request would let the host discard a result they dislike
+        require!(
+            board.vrf_account == Pubkey::default(),
+            SquaresError::RandomizationAlreadyRequested
+        );
+
+        check_vrf_request(
+            &ctx.accounts.vrf_account,
+            &board.key(),
+            &ctx.accounts.oracle_registry,
+            &clock,
+        )?;
+
+        // Bind the board to this VRF account; only a result it produces after
+        // this slot will be accepted by fulfill_vrf_callback.
+        board.vrf_account = ctx.accounts.vrf_account.key();
+        board.vrf_requested_slot = clock.slot;
 
         // This will be called by Clockwork thread or agent
         emit!(RandomizationRequested {
             board_id: board.game_id,
-            vrf_account: *ctx.accounts.vrf_account.key,
+            vrf_account: ctx.accounts.vrf_account.key(),
         });
 
         msg!("Randomization requested for board #{}", board.game_id);
@@ -78,16 +411,28 @@ pub mod squares {
 
     pub fn fulfill_vrf_callback(ctx: Context<FulfillVrf>, randomness: [u8; 32]) -> Result<()> {
         let board = &mut ctx.accounts.board;
-        
-        require!(!board.randomized, SquaresError::AlreadyRandomized);
+        let clock = Clock::get()?;
 
-        // TODO: Re-implement VRF verification when switchboard dependency is resolved
-        // For now, we'll accept the randomness directly
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        require!(board.randomness_mode == RandomnessMode::Vrf, SquaresError::WrongRandomnessMode);
+        require!(!board.is_randomized(), SquaresError::AlreadyRandomized);
+
+        // The result must come from the VRF account bound at request time and
+        // must have been produced after the request, so a result the host
+        // already knew cannot be replayed onto the board.
+        let result = read_vrf_result(
+            &ctx.accounts.vrf_account,
+            &board.key(),
+            board.vrf_requested_slot,
+            &ctx.accounts.oracle_registry,
+            &clock,
+        )?;
+        require!(result == randomness, SquaresError::InvalidVrfProof);
 
         // Derive headers from randomness
-        board.home_headers = derive_headers(&randomness[..16]);
-        board.away_headers = derive_headers(&randomness[16..]);
-        board.randomized = true;
+        board.home_headers = derive_headers(&randomness, HOME_HEADER_DOMAIN);
+        board.away_headers = derive_headers(&randomness, AWAY_HEADER_DOMAIN);
+        board.transition(BoardStatus::Randomized)?;
 
         emit!(HeadersRandomized {
             board_id: board.game_id,
@@ -99,340 +444,3198 @@ pub mod squares {
         Ok(())
     }
 
-    pub fn purchase_square(ctx: Context<PurchaseSquare>, square_index: u8) -> Result<()> {
-        {
-            let board = &ctx.accounts.board;
-            require!(board.randomized, SquaresError::NotRandomized);
-            require!(!board.game_started, SquaresError::GameAlreadyStarted);
-            require!(square_index < 100, SquaresError::InvalidSquareIndex);
-            require!(
-                board.squares[square_index as usize] == Pubkey::default(),
-                SquaresError::SquareAlreadyOwned
-            );
-        }
+    pub fn close_sales(ctx: Context<CloseSales>) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let clock = Clock::get()?;
 
-        // Transfer SOL to board account
-        let transfer_instruction = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.buyer.to_account_info(),
-            to: ctx.accounts.board.to_account_info(),
-        };
-        let transfer_ctx = CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_instruction,
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        // Anyone may close a full board or one past its cutoff; before that only the host may
+        require!(
+            board.squares_sold() == board.grid_size.square_count()
+                || clock.unix_timestamp >= board.sales_close_at
+                || ctx.accounts.closer.key() == board.authority,
+            SquaresError::SalesStillOpen
         );
-        anchor_lang::system_program::transfer(transfer_ctx, 10_000_000)?; // 0.01 SOL
 
+        match board.header_mode {
+            HeaderMode::SalesFirst => {
+                require!(board.status == BoardStatus::Open, SquaresError::SalesClosed);
+                board.transition(BoardStatus::SalesClosed)?;
+                board.sales_closed_slot = clock.slot;
+            }
+            // Headers-first boards sell while Randomized, so closing just pulls the cutoff in
+            HeaderMode::HeadersFirst => {
+                require!(board.is_randomized(), SquaresError::NotRandomized);
+                require!(!board.game_started(), SquaresError::GameAlreadyStarted);
+                require!(clock.unix_timestamp < board.sales_close_at, SquaresError::SalesClosed);
+                board.sales_close_at = clock.unix_timestamp;
+            }
+        }
+
+        emit!(SalesClosed {
+            board_id: board.game_id,
+            squares_sold: board.squares_sold(),
+        });
+
+        msg!("Sales closed for board #{}", board.game_id);
+        Ok(())
+    }
+
+    pub fn record_closing_slot_hash(ctx: Context<RecordClosingSlotHash>) -> Result<()> {
         let board = &mut ctx.accounts.board;
-        board.squares[square_index as usize] = *ctx.accounts.buyer.key;
-        board.total_pot += 10_000_000;
 
-        emit!(SquarePurchased {
+        require!(
+            matches!(board.randomness_mode, RandomnessMode::CommitReveal { .. }),
+            SquaresError::WrongRandomnessMode
+        );
+        require!(board.status == BoardStatus::SalesClosed, SquaresError::SalesStillOpen);
+        require!(board.sales_closed_slot_hash == [0; 32], SquaresError::ClosingSlotHashRecorded);
+
+        // SlotHashes forgets a slot after a few minutes, long before most reveal
+        // deadlines, so anyone may pin the closing slot's hash while it is there
+        board.sales_closed_slot_hash = slot_hash_at(&ctx.accounts.slot_hashes, board.sales_closed_slot)?;
+
+        emit!(ClosingSlotHashRecorded {
             board_id: board.game_id,
-            square_index,
-            buyer: *ctx.accounts.buyer.key,
-            amount: 10_000_000,
+            sales_closed_slot: board.sales_closed_slot,
+            slot_hash: board.sales_closed_slot_hash,
         });
 
-        msg!("Square {} purchased for board #{}", square_index, board.game_id);
+        msg!("Closing slot hash recorded for board #{}", board.game_id);
         Ok(())
     }
 
-    pub fn record_score(ctx: Context<RecordScore>, home_score: u8, away_score: u8, quarter: u8) -> Result<()> {
+    pub fn reveal_seed(ctx: Context<RevealSeed>, seed: [u8; 32]) -> Result<()> {
         let board = &mut ctx.accounts.board;
-        
-        require!(board.randomized, SquaresError::NotRandomized);
-        require!(!board.game_ended, SquaresError::GameEnded);
-        require!(quarter <= 4, SquaresError::InvalidQuarter);
+        let clock = Clock::get()?;
 
-        board.home_score = home_score;
-        board.away_score = away_score;
-        board.quarter = quarter;
+        let (seed_commitment, reveal_deadline) = match board.randomness_mode {
+            RandomnessMode::CommitReveal { seed_commitment, reveal_deadline } => {
+                (seed_commitment, reveal_deadline)
+            }
+            RandomnessMode::Vrf => return Err(SquaresError::WrongRandomnessMode.into()),
+        };
 
-        if !board.game_started {
-            board.game_started = true;
-        }
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        require!(!board.is_randomized(), SquaresError::AlreadyRandomized);
+        require!(board.status == BoardStatus::SalesClosed, SquaresError::SalesStillOpen);
+        require!(clock.unix_timestamp <= reveal_deadline, SquaresError::RevealDeadlinePassed);
+        require!(hashv(&[&seed]).to_bytes() == seed_commitment, SquaresError::InvalidSeedReveal);
 
-        if quarter == 4 {
-            board.game_ended = true;
+        // Mixing in the buyers' entropy means the host could not have known the
+        // final headers when committing, and no buyer controls them alone. The
+        // closing slot's hash did not exist yet when the last square sold or
+        // sales closed, so nobody who knows the seed can grind that moment.
+        if board.sales_closed_slot_hash == [0; 32] {
+            board.sales_closed_slot_hash = slot_hash_at(&ctx.accounts.slot_hashes, board.sales_closed_slot)?;
         }
+        let closing_slot_hash = board.sales_closed_slot_hash;
+        let randomness = hashv(&[&seed, &board.entropy, &closing_slot_hash]).to_bytes();
+        board.home_headers = derive_headers(&randomness, HOME_HEADER_DOMAIN);
+        board.away_headers = derive_headers(&randomness, AWAY_HEADER_DOMAIN);
+        board.transition(BoardStatus::Randomized)?;
 
-        emit!(ScoreRecorded {
+        emit!(HeadersRandomized {
             board_id: board.game_id,
-            home_score,
-            away_score,
-            quarter,
+            home_headers: board.home_headers,
+            away_headers: board.away_headers,
         });
 
-        msg!("Score recorded: {}:{} Q{} for board #{}", home_score, away_score, quarter, board.game_id);
+        msg!("Seed revealed for board #{}", board.game_id);
         Ok(())
     }
 
-    pub fn settle_winner(ctx: Context<SettleWinner>) -> Result<()> {
+    pub fn cancel_board(ctx: Context<CancelBoard>) -> Result<()> {
         let board = &mut ctx.accounts.board;
-        
-        require!(board.game_ended, SquaresError::GameNotEnded);
-        require!(board.winner == Pubkey::default(), SquaresError::AlreadySettled);
-
-        let home_digit = board.home_score % 10;
-        let away_digit = board.away_score % 10;
+        let clock = Clock::get()?;
 
-        // Find the winner based on the final score digits
-        let winner_square = find_winner_square(
-            &board.home_headers,
-            &board.away_headers,
-            home_digit,
-            away_digit,
-        )?;
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        // Refunds return the full purchase price, so nothing may have been paid out
+        require!(
+            board.periods.iter().all(|period| !period.settled),
+            SquaresError::AlreadySettled
+        );
 
-        let winner_address = board.squares[winner_square as usize];
-        require!(winner_address != Pubkey::default(), SquaresError::NoWinner);
+        // Once kickoff passes, the host could cancel a board they were losing
+        let is_host = ctx.accounts.canceller.key() == board.authority;
+        let reason = if is_host && !board.game_started() && clock.unix_timestamp < board.kickoff_at {
+            CancelReason::HostCancelled
+        } else if clock.unix_timestamp > board.settle_deadline {
+            // Game postponed, board never filled or randomness never arrived
+            CancelReason::SettleDeadlinePassed
+        } else {
+            return Err(SquaresError::CannotCancel.into());
+        };
 
-        board.winner = winner_address;
-        board.payout_amount = board.total_pot;
+        board.transition(BoardStatus::Cancelled)?;
 
-        emit!(WinnerSettled {
+        emit!(BoardCancelled {
             board_id: board.game_id,
-            winner: winner_address,
-            payout_amount: board.payout_amount,
-            square_index: winner_square,
+            cancelled_by: ctx.accounts.canceller.key(),
+            reason,
+            total_pot: board.total_pot,
         });
 
-        msg!("Winner settled for board #{}: {} wins {} lamports",
-             board.game_id, winner_address, board.payout_amount);
+        msg!("Board #{} cancelled", board.game_id);
         Ok(())
     }
 
-    pub fn payout_winner(ctx: Context<PayoutWinner>) -> Result<()> {
-        let payout_amount;
+    pub fn claim_refund(ctx: Context<ClaimRefund>, square_index: u8) -> Result<()> {
+        let owner_key = ctx.accounts.owner.key();
         let game_id;
-        let winner_key = *ctx.accounts.winner.key;
-        
+        let refund_amount;
+        let mut co_owner_claim = None;
+
         {
             let board = &ctx.accounts.board;
-            require!(board.winner != Pubkey::default(), SquaresError::NoWinner);
-            require!(board.payout_amount > 0, SquaresError::NoPayout);
-            require!(board.winner == winner_key, SquaresError::InvalidWinner);
-            payout_amount = board.payout_amount;
+            let clock = Clock::get()?;
+
+            require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+            require!(board.is_refundable(clock.unix_timestamp), SquaresError::NotRefundable);
+            require!(
+                board.refunded_squares & (1u128 << square_index) == 0,
+                SquaresError::AlreadyRefunded
+            );
             game_id = board.game_id;
+            // The price is locked after the first sale, so this is what the owner paid
+            let price = board.price_per_square;
+
+            // Each co-owner of a shared square reclaims their weighted part separately
+            if board.is_shared(square_index) {
+                let shares = shared_square(board, square_index, ctx.accounts.owner_shares.as_deref())?;
+                let position = shares.position_of(&owner_key).ok_or(SquaresError::NotSquareOwner)?;
+                require!(shares.refunds_claimed & (1 << position) == 0, SquaresError::AlreadyRefunded);
+                co_owner_claim = Some(shares.refunds_claimed | (1 << position));
+                refund_amount = shares.share_of(position, price);
+            } else {
+                require_square_holder(board, square_index, &owner_key, ctx.accounts.owner_token_account.as_deref())?;
+                refund_amount = price;
+            }
         }
 
-        // Transfer winnings to winner
-        **ctx.accounts.board.to_account_info().try_borrow_mut_lamports()? -= payout_amount;
-        **ctx.accounts.winner.to_account_info().try_borrow_mut_lamports()? += payout_amount;
+        **ctx.accounts.board.to_account_info().try_borrow_mut_lamports()? -= refund_amount;
+        **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += refund_amount;
+
+        // A shared square counts as refunded once every co-owner has claimed
+        let mut refunded = true;
+        if let Some(claimed) = co_owner_claim {
+            let shares = ctx.accounts.owner_shares.as_deref_mut().unwrap();
+            shares.refunds_claimed = claimed;
+            refunded = shares.all_claimed(claimed);
+        }
 
-        // Mark as paid
         let board = &mut ctx.accounts.board;
-        board.payout_amount = 0;
+        if refunded {
+            board.refunded_squares |= 1u128 << square_index;
+        }
+        board.total_pot -= refund_amount;
 
-        emit!(WinnerPaid {
+        emit!(RefundClaimed {
             board_id: game_id,
-            winner: winner_key,
-            amount: payout_amount,
-        });
-
-        msg!("Winner paid for board #{}", game_id);
-        Ok(())
-    }
-
-    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
-        let treasury = &mut ctx.accounts.treasury;
-        treasury.authority = *ctx.accounts.authority.key;
-        treasury.total_collected = 0;
-        treasury.bump = ctx.bumps.treasury;
-
-        emit!(TreasuryInitialized {
-            authority: *ctx.accounts.authority.key,
+            square_index,
+            owner: owner_key,
+            amount: refund_amount,
         });
 
-        msg!("Treasury initialized!");
+        msg!("Square {} refunded for board #{}", square_index, game_id);
         Ok(())
     }
 
-    pub fn boost_board(ctx: Context<BoostBoard>, duration_days: u8) -> Result<()> {
-        let board = &mut ctx.accounts.board;
-        let clock = Clock::get()?;
-
-        // Validate duration
-        require!(
-            duration_days == 1 || duration_days == 3 || duration_days == 7,
-            SquaresError::InvalidBoostDuration
-        );
+    pub fn purchase_square(ctx: Context<PurchaseSquare>, square_index: u8) -> Result<()> {
+        let price;
 
-        // Calculate boost fee based on duration
-        let base_fee = match duration_days {
-            1 => 50_000_000,   // 0.05 SOL for 1 day
-            3 => 120_000_000,  // 0.12 SOL for 3 days  
-            7 => 250_000_000,  // 0.25 SOL for 7 days
-            _ => return Err(SquaresError::InvalidBoostDuration.into()),
-        };
+        {
+            let board = &ctx.accounts.board;
+            let clock = Clock::get()?;
+            require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+            validate_sales_open(board, clock.unix_timestamp)?;
+            require!(
+                board.squares[square_index as usize] == Pubkey::default(),
+                SquaresError::SquareAlreadyOwned
+            );
+            require!(!board.is_held(square_index), SquaresError::SquareHeld);
+            price = board.price_per_square;
+        }
+        count_wallet_squares(
+            &ctx.accounts.board,
+            &mut ctx.accounts.wallet_squares,
+            ctx.accounts.buyer.key(),
+            ctx.bumps.wallet_squares,
+            1,
+        )?;
 
-        // Transfer SOL from CBL to treasury
-        let transfer_ix = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.authority.to_account_info(),
-            to: ctx.accounts.treasury.to_account_info(),
+        // Transfer SOL to board account
+        let transfer_instruction = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: ctx.accounts.board.to_account_info(),
         };
-        let cpi_ctx = CpiContext::new(
+        let transfer_ctx = CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
-            transfer_ix,
+            transfer_instruction,
         );
-        anchor_lang::system_program::transfer(cpi_ctx, base_fee)?;
+        anchor_lang::system_program::transfer(transfer_ctx, price)?;
 
-        // Update board boost data
-        board.boost_amount += base_fee;
-        board.boost_expires_at = clock.unix_timestamp + (duration_days as i64 * 86400);
+        mint_square(
+            &mut ctx.accounts.board,
+            &ctx.accounts.square_mint.to_account_info(),
+            &ctx.accounts.square_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            ctx.accounts.buyer.key(),
+            square_index,
+        )?;
 
-        // Update treasury
-        let treasury = &mut ctx.accounts.treasury;
-        treasury.total_collected += base_fee;
+        let clock = Clock::get()?;
+        let board = &mut ctx.accounts.board;
+        assign_square(board, ctx.accounts.buyer.key, square_index, clock.slot);
+        board.total_pot += price;
 
-        emit!(BoardBoosted {
-            game_id: board.game_id,
-            boost_amount: base_fee,
-            expires_at: board.boost_expires_at,
-            booster: *ctx.accounts.authority.key,
+        emit!(SquarePurchased {
+            board_id: board.game_id,
+            square_index,
+            buyer: *ctx.accounts.buyer.key,
+            amount: price,
         });
 
-        msg!("Board #{} boosted for {} days with {} lamports!", 
-             board.game_id, duration_days, base_fee);
+        msg!("Square {} purchased for board #{}", square_index, board.game_id);
+        Ok(())
+    }
+
+    pub fn purchase_squares<'info>(
+        ctx: Context<'_, '_, '_, 'info, PurchaseSquares<'info>>,
+        square_indices: Vec<u8>,
+    ) -> Result<()> {
+        {
+            let board = &ctx.accounts.board;
+            let clock = Clock::get()?;
+            require!(!square_indices.is_empty(), SquaresError::InvalidSquareIndex);
+            validate_sales_open(board, clock.unix_timestamp)?;
+
+            // Every square must be valid and free, or nothing is bought
+            let mut requested = 0u128;
+            for &square_index in &square_indices {
+                require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+                require!(requested & (1u128 << square_index) == 0, SquaresError::DuplicateSquare);
+                require!(
+                    board.squares[square_index as usize] == Pubkey::default(),
+                    SquaresError::SquareAlreadyOwned
+                );
+                require!(!board.is_held(square_index), SquaresError::SquareHeld);
+                requested |= 1u128 << square_index;
+            }
+        }
+
+        let accounts = ctx.accounts;
+        count_wallet_squares(
+            &accounts.board,
+            &mut accounts.wallet_squares,
+            accounts.buyer.key(),
+            ctx.bumps.wallet_squares,
+            square_indices.len(),
+        )?;
+        mint_sold_squares(
+            &mut accounts.board,
+            &accounts.buyer,
+            &accounts.token_program,
+            &accounts.associated_token_program,
+            &accounts.system_program,
+            ctx.remaining_accounts,
+            &square_indices,
+        )?;
+        sell_squares(&mut accounts.board, &accounts.buyer, &accounts.system_program, square_indices)
+    }
+
+    pub fn purchase_shared_square(
+        ctx: Context<PurchaseSharedSquare>,
+        square_index: u8,
+        co_owners: Vec<CoOwner>,
+    ) -> Result<()> {
+        {
+            let board = &ctx.accounts.board;
+            let clock = Clock::get()?;
+            require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+            validate_sales_open(board, clock.unix_timestamp)?;
+            require!(
+                board.squares[square_index as usize] == Pubkey::default(),
+                SquaresError::SquareAlreadyOwned
+            );
+            require!(!board.is_held(square_index), SquaresError::SquareHeld);
+
+            require!(
+                (2..=MAX_CO_OWNERS).contains(&co_owners.len()),
+                SquaresError::InvalidCoOwners
+            );
+            for (i, co_owner) in co_owners.iter().enumerate() {
+                require!(
+                    co_owner.weight > 0
+                        && co_owner.owner != Pubkey::default()
+                        && co_owners[..i].iter().all(|other| other.owner != co_owner.owner),
+                    SquaresError::InvalidCoOwners
+                );
+            }
+        }
+
+        let accounts = ctx.accounts;
+        count_wallet_squares(
+            &accounts.board,
+            &mut accounts.wallet_squares,
+            accounts.buyer.key(),
+            ctx.bumps.wallet_squares,
+            1,
+        )?;
+        sell_squares(&mut accounts.board, &accounts.buyer, &accounts.system_program, vec![square_index])?;
+        mint_square(
+            &mut accounts.board,
+            &accounts.square_mint.to_account_info(),
+            &accounts.square_token_account.to_account_info(),
+            &accounts.token_program,
+            accounts.buyer.key(),
+            square_index,
+        )?;
+
+        // Co-owners are paid from their shares, so the token stays frozen as a receipt
+        let game_id = accounts.board.game_id;
+        let board_seeds: &[&[&[u8]]] = &[&[b"board", &game_id.to_le_bytes(), &[accounts.board.bump]]];
+        token_2022::freeze_account(CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            FreezeAccount {
+                account: accounts.square_token_account.to_account_info(),
+                mint: accounts.square_mint.to_account_info(),
+                authority: accounts.board.to_account_info(),
+            },
+            board_seeds,
+        ))?;
+
+        let board = &mut accounts.board;
+        board.shared_squares |= 1u128 << square_index;
+
+        let shares = &mut accounts.square_shares;
+        shares.board = board.key();
+        shares.square_index = square_index;
+        shares.co_owners = co_owners.clone();
+        shares.refunds_claimed = 0;
+        shares.period_shares_claimed = [0; PERIODS];
+        shares.bump = ctx.bumps.square_shares;
+
+        emit!(SquareShared {
+            board_id: board.game_id,
+            square_index,
+            co_owners,
+        });
+
+        msg!("Square {} on board #{} shared among {} co-owners",
+             square_index, board.game_id, shares.co_owners.len());
+        Ok(())
+    }
+
+    pub fn quick_pick(ctx: Context<QuickPick>, count: u8) -> Result<()> {
+        let clock = Clock::get()?;
+
+        {
+            let board = &ctx.accounts.board;
+            require!(count > 0, SquaresError::InvalidSquareIndex);
+            validate_sales_open(board, clock.unix_timestamp)?;
+            require!(board.available_squares().len() >= count as usize, SquaresError::NotEnoughSquares);
+        }
+        count_wallet_squares(
+            &ctx.accounts.board,
+            &mut ctx.accounts.wallet_squares,
+            ctx.accounts.buyer.key(),
+            ctx.bumps.wallet_squares,
+            count as usize,
+        )?;
+
+        // The squares are only drawn once this slot's hash exists, so the
+        // buyer pays up front and cannot back out of a draw they dislike
+        let board = &ctx.accounts.board;
+        let deposit = board.price_per_square * count as u64;
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.ticket.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, deposit)?;
+
+        let ticket = &mut ctx.accounts.ticket;
+        ticket.board = board.key();
+        ticket.buyer = ctx.accounts.buyer.key();
+        ticket.count = count;
+        ticket.price_per_square = board.price_per_square;
+        ticket.reserved_slot = clock.slot;
+        ticket.bump = ctx.bumps.ticket;
+
+        emit!(QuickPickReserved {
+            board_id: board.game_id,
+            buyer: ticket.buyer,
+            count,
+            reserved_slot: ticket.reserved_slot,
+        });
+
+        msg!("{} quick picks reserved on board #{} in slot {}", count, board.game_id, ticket.reserved_slot);
+        Ok(())
+    }
+
+    pub fn fill_quick_pick<'info>(ctx: Context<'_, '_, '_, 'info, FillQuickPick<'info>>) -> Result<()> {
+        let ticket = &ctx.accounts.ticket;
+        let square_indices;
+
+        {
+            let board = &ctx.accounts.board;
+            let clock = Clock::get()?;
+            require!(!board.is_refundable(clock.unix_timestamp), SquaresError::BoardCancelled);
+            require!(!board.game_started(), SquaresError::GameAlreadyStarted);
+
+            // The hash of the reservation slot did not exist when the buyer
+            // reserved, so they could not steer which squares they get
+            let slot_hash = slot_hash_at(&ctx.accounts.slot_hashes, ticket.reserved_slot)?;
+            let seed = quick_pick_seed(&slot_hash, &board.key(), &ticket.buyer);
+            let available = board.available_squares();
+            // Squares sold since the reservation shrink the draw; the rest is refunded
+            let count = available.len().min(ticket.count as usize);
+            square_indices = pick_squares(&available, count, &seed);
+        }
+
+        let accounts = ctx.accounts;
+        mint_sold_squares(
+            &mut accounts.board,
+            &accounts.buyer,
+            &accounts.token_program,
+            &accounts.associated_token_program,
+            &accounts.system_program,
+            ctx.remaining_accounts,
+            &square_indices,
+        )?;
+
+        let unfilled = accounts.ticket.count - square_indices.len() as u8;
+        accounts.wallet_squares.squares_owned -= unfilled;
+
+        // Whatever is not spent here returns to the buyer when the ticket closes
+        let total = accounts.ticket.price_per_square * square_indices.len() as u64;
+        **accounts.ticket.to_account_info().try_borrow_mut_lamports()? -= total;
+        **accounts.board.to_account_info().try_borrow_mut_lamports()? += total;
+        record_sale(&mut accounts.board, accounts.buyer.key, square_indices, total)
+    }
+
+    pub fn release_quick_pick(ctx: Context<ReleaseQuickPick>) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let ticket = &ctx.accounts.ticket;
+        let clock = Clock::get()?;
+
+        let refunded = board.is_refundable(clock.unix_timestamp) || board.game_started();
+        // Otherwise the buyer let the draw age out of SlotHashes unfilled
+        require!(
+            refunded || clock.slot > ticket.reserved_slot + SLOT_HASHES_LEN,
+            SquaresError::QuickPickPending
+        );
+
+        let deposit = ticket.price_per_square * ticket.count as u64;
+        if !refunded {
+            **ctx.accounts.ticket.to_account_info().try_borrow_mut_lamports()? -= deposit;
+            **board.to_account_info().try_borrow_mut_lamports()? += deposit;
+            board.total_pot += deposit;
+        }
+        ctx.accounts.wallet_squares.squares_owned -= ticket.count;
+
+        emit!(QuickPickReleased {
+            board_id: board.game_id,
+            buyer: ticket.buyer,
+            released_by: ctx.accounts.releaser.key(),
+            deposit,
+            forfeited: !refunded,
+        });
+
+        msg!("Quick pick for {} released on board #{}", ticket.buyer, board.game_id);
+        Ok(())
+    }
+
+    pub fn close_wallet_squares(ctx: Context<CloseWalletSquares>) -> Result<()> {
+        let board = &ctx.accounts.board;
+
+        // Wallet limits only matter while squares can still be bought or moved
+        require!(
+            matches!(board.status, BoardStatus::Paid | BoardStatus::Cancelled),
+            SquaresError::BoardStillActive
+        );
+
+        msg!("Square counter for {} closed on board #{}", ctx.accounts.wallet.key(), board.game_id);
+        Ok(())
+    }
+
+    pub fn hold_square(ctx: Context<HoldSquare>, square_index: u8) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let clock = Clock::get()?;
+
+        require!(board.hold_duration_secs > 0, SquaresError::HoldsDisabled);
+        require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+        validate_sales_open(board, clock.unix_timestamp)?;
+        require!(
+            board.squares[square_index as usize] == Pubkey::default(),
+            SquaresError::SquareAlreadyOwned
+        );
+        require!(!board.is_held(square_index), SquaresError::SquareHeld);
+
+        // The deposit sits in the hold account until it is used or released
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.holder.to_account_info(),
+                to: ctx.accounts.hold.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, board.hold_deposit)?;
+
+        let hold = &mut ctx.accounts.hold;
+        hold.board = board.key();
+        hold.square_index = square_index;
+        hold.holder = ctx.accounts.holder.key();
+        hold.deposit = board.hold_deposit;
+        hold.expires_at = clock.unix_timestamp + board.hold_duration_secs;
+        hold.bump = ctx.bumps.hold;
+
+        board.held_squares |= 1u128 << square_index;
+
+        emit!(SquareHeld {
+            board_id: board.game_id,
+            square_index,
+            holder: hold.holder,
+            deposit: hold.deposit,
+            expires_at: hold.expires_at,
+        });
+
+        msg!("Square {} held on board #{} until {}", square_index, board.game_id, hold.expires_at);
+        Ok(())
+    }
+
+    pub fn purchase_held_square(ctx: Context<PurchaseHeldSquare>) -> Result<()> {
+        let square_index = ctx.accounts.hold.square_index;
+        let price;
+
+        {
+            let board = &ctx.accounts.board;
+            let clock = Clock::get()?;
+            require!(clock.unix_timestamp < ctx.accounts.hold.expires_at, SquaresError::HoldExpired);
+            validate_sales_open(board, clock.unix_timestamp)?;
+            price = board.price_per_square;
+        }
+        count_wallet_squares(
+            &ctx.accounts.board,
+            &mut ctx.accounts.wallet_squares,
+            ctx.accounts.holder.key(),
+            ctx.bumps.wallet_squares,
+            1,
+        )?;
+
+        // The deposit comes back with the hold account when it closes
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.holder.to_account_info(),
+                to: ctx.accounts.board.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, price)?;
+
+        mint_square(
+            &mut ctx.accounts.board,
+            &ctx.accounts.square_mint.to_account_info(),
+            &ctx.accounts.square_token_account.to_account_info(),
+            &ctx.accounts.token_program,
+            ctx.accounts.holder.key(),
+            square_index,
+        )?;
+
+        let clock = Clock::get()?;
+        let board = &mut ctx.accounts.board;
+        board.held_squares &= !(1u128 << square_index);
+        assign_square(board, ctx.accounts.holder.key, square_index, clock.slot);
+        board.total_pot += price;
+
+        emit!(SquarePurchased {
+            board_id: board.game_id,
+            square_index,
+            buyer: ctx.accounts.holder.key(),
+            amount: price,
+        });
+
+        msg!("Held square {} purchased for board #{}", square_index, board.game_id);
+        Ok(())
+    }
+
+    pub fn release_hold(ctx: Context<ReleaseHold>) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let hold = &ctx.accounts.hold;
+        let clock = Clock::get()?;
+
+        let expired = clock.unix_timestamp >= hold.expires_at;
+        // Anyone may clear an expired hold; the holder may give one up early
+        require!(
+            expired
+                || ctx.accounts.releaser.key() == hold.holder
+                || board.status == BoardStatus::Cancelled,
+            SquaresError::HoldActive
+        );
+
+        // Deposits only feed the pot of a board still selling squares; once
+        // sales end or the board is cancelled they go back to the holder
+        let forfeited = expired
+            && board.expired_hold_policy == ExpiredHoldPolicy::ForfeitToPot
+            && validate_sales_open(board, clock.unix_timestamp).is_ok();
+        if forfeited {
+            **ctx.accounts.hold.to_account_info().try_borrow_mut_lamports()? -= hold.deposit;
+            **board.to_account_info().try_borrow_mut_lamports()? += hold.deposit;
+            board.total_pot += hold.deposit;
+        }
+        board.held_squares &= !(1u128 << hold.square_index);
+
+        emit!(HoldReleased {
+            board_id: board.game_id,
+            square_index: hold.square_index,
+            holder: hold.holder,
+            released_by: ctx.accounts.releaser.key(),
+            deposit: hold.deposit,
+            forfeited,
+        });
+
+        msg!("Hold on square {} released for board #{}", hold.square_index, board.game_id);
+        Ok(())
+    }
+
+    pub fn transfer_square(ctx: Context<TransferSquare>, square_index: u8, recipient: Pubkey) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        let clock = Clock::get()?;
+
+        validate_resale(
+            &ctx.accounts.board,
+            square_index,
+            &owner,
+            ctx.accounts.owner_token_account.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        require!(recipient != Pubkey::default() && recipient != owner, SquaresError::InvalidRecipient);
+
+        if ctx.accounts.board.is_tokenized(square_index) {
+            let accounts = &ctx.accounts;
+            let to = accounts.recipient_token_account.as_deref().ok_or(SquaresError::SquareTokenized)?;
+            require!(to.owner == recipient, SquaresError::InvalidRecipient);
+            // The owner signs this instruction, so their signature moves the token
+            move_square_token(
+                accounts.square_mint.as_deref(),
+                accounts.owner_token_account.as_deref(),
+                to,
+                &accounts.owner.to_account_info(),
+                &accounts.token_program,
+                &[],
+            )?;
+        }
+
+        // A holder who got the token outside the program has no counter yet
+        count_wallet_squares(
+            &ctx.accounts.board,
+            &mut ctx.accounts.owner_wallet_squares,
+            owner,
+            ctx.bumps.owner_wallet_squares,
+            0,
+        )?;
+        move_square(
+            &mut ctx.accounts.board,
+            &mut ctx.accounts.owner_wallet_squares,
+            &mut ctx.accounts.recipient_wallet_squares,
+            ctx.bumps.recipient_wallet_squares,
+            square_index,
+            recipient,
+        )?;
+
+        let board = &ctx.accounts.board;
+        emit!(SquareTransferred {
+            board_id: board.game_id,
+            square_index,
+            from: owner,
+            to: recipient,
+            price: 0,
+            royalty: 0,
+        });
+
+        msg!("Square {} on board #{} transferred to {}", square_index, board.game_id, recipient);
+        Ok(())
+    }
+
+    pub fn list_square(ctx: Context<ListSquare>, square_index: u8, ask: u64) -> Result<()> {
+        let seller = ctx.accounts.seller.key();
+        let clock = Clock::get()?;
+
+        validate_resale(
+            &ctx.accounts.board,
+            square_index,
+            &seller,
+            ctx.accounts.seller_token_account.as_deref(),
+            clock.unix_timestamp,
+        )?;
+        require!(ask > 0, SquaresError::InvalidPrice);
+
+        // The listing may move the token when the listing is filled
+        if let Some(seller_token_account) = &ctx.accounts.seller_token_account {
+            token_interface::approve(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Approve {
+                        to: seller_token_account.to_account_info(),
+                        delegate: ctx.accounts.listing.to_account_info(),
+                        authority: ctx.accounts.seller.to_account_info(),
+                    },
+                ),
+                1,
+            )?;
+        }
+
+        let listing = &mut ctx.accounts.listing;
+        listing.board = ctx.accounts.board.key();
+        listing.square_index = square_index;
+        listing.seller = seller;
+        listing.ask = ask;
+        listing.bump = ctx.bumps.listing;
+
+        emit!(SquareListed {
+            board_id: ctx.accounts.board.game_id,
+            square_index,
+            seller,
+            ask,
+        });
+
+        msg!("Square {} listed on board #{} for {} lamports", square_index, ctx.accounts.board.game_id, ask);
+        Ok(())
+    }
+
+    pub fn cancel_listing(ctx: Context<CancelListing>) -> Result<()> {
+        let listing = &ctx.accounts.listing;
+        let canceller = ctx.accounts.canceller.key();
+
+        if canceller == listing.seller {
+            if let Some(seller_token_account) = &ctx.accounts.token_account {
+                require!(seller_token_account.owner == listing.seller, SquaresError::NotSquareOwner);
+                token_interface::revoke(CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Revoke {
+                        source: seller_token_account.to_account_info(),
+                        authority: ctx.accounts.canceller.to_account_info(),
+                    },
+                ))?;
+            }
+        } else {
+            // The square has moved on since it was listed; its new owner may
+            // clear the listing so it no longer blocks their own
+            require_square_holder(
+                &ctx.accounts.board,
+                listing.square_index,
+                &canceller,
+                ctx.accounts.token_account.as_deref(),
+            )?;
+        }
+
+        emit!(ListingCancelled {
+            board_id: ctx.accounts.board.game_id,
+            square_index: listing.square_index,
+            seller: listing.seller,
+            cancelled_by: canceller,
+        });
+
+        msg!("Listing for square {} cancelled", listing.square_index);
+        Ok(())
+    }
+
+    pub fn fill_listing(ctx: Context<FillListing>) -> Result<()> {
+        let buyer = ctx.accounts.buyer.key();
+        let seller = ctx.accounts.listing.seller;
+        let square_index = ctx.accounts.listing.square_index;
+        let ask = ctx.accounts.listing.ask;
+        let clock = Clock::get()?;
+
+        // The seller may have given the square away since listing it
+        validate_resale(
+            &ctx.accounts.board,
+            square_index,
+            &seller,
+            ctx.accounts.seller_token_account.as_deref(),
+            clock.unix_timestamp,
+        )
+        .map_err(|_| error!(SquaresError::ListingStale))?;
+        require!(buyer != seller, SquaresError::InvalidRecipient);
+
+        if ctx.accounts.board.is_tokenized(square_index) {
+            let accounts = &ctx.accounts;
+            let to = accounts.buyer_token_account.as_deref().ok_or(SquaresError::SquareTokenized)?;
+            require!(to.owner == buyer, SquaresError::InvalidRecipient);
+            // The seller approved the listing as the token's delegate when listing it
+            let board_key = accounts.board.key();
+            let listing_seeds: &[&[&[u8]]] = &[&[
+                b"listing",
+                board_key.as_ref(),
+                &[square_index],
+                &[accounts.listing.bump],
+            ]];
+            move_square_token(
+                accounts.square_mint.as_deref(),
+                accounts.seller_token_account.as_deref(),
+                to,
+                &accounts.listing.to_account_info(),
+                &accounts.token_program,
+                listing_seeds,
+            )?;
+        }
+
+        let royalty = (ask as u128 * ctx.accounts.board.resale_royalty_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        for (to, amount) in [
+            (ctx.accounts.seller.to_account_info(), ask - royalty),
+            (ctx.accounts.host.to_account_info(), royalty),
+        ] {
+            if amount > 0 {
+                let transfer_ctx = CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.buyer.to_account_info(),
+                        to,
+                    },
+                );
+                anchor_lang::system_program::transfer(transfer_ctx, amount)?;
+            }
+        }
+
+        count_wallet_squares(
+            &ctx.accounts.board,
+            &mut ctx.accounts.seller_wallet_squares,
+            seller,
+            ctx.bumps.seller_wallet_squares,
+            0,
+        )?;
+        move_square(
+            &mut ctx.accounts.board,
+            &mut ctx.accounts.seller_wallet_squares,
+            &mut ctx.accounts.buyer_wallet_squares,
+            ctx.bumps.buyer_wallet_squares,
+            square_index,
+            buyer,
+        )?;
+
+        let board = &ctx.accounts.board;
+        emit!(SquareTransferred {
+            board_id: board.game_id,
+            square_index,
+            from: seller,
+            to: buyer,
+            price: ask,
+            royalty,
+        });
+
+        msg!("Square {} on board #{} sold for {} lamports", square_index, board.game_id, ask);
+        Ok(())
+    }
+
+    pub fn record_score(
+        ctx: Context<RecordScore>,
+        home_score: u8,
+        away_score: u8,
+        quarter: u8,
+        is_final: bool,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        
+        // Quorum boards only take scores through submit_score
+        require!(board.score_quorum == 1, SquaresError::QuorumRequired);
+        validate_score_report(board, home_score, away_score, quarter, is_final)?;
+        apply_score(board, home_score, away_score, quarter, is_final, 1)
+    }
+
+    pub fn record_signed_score(
+        ctx: Context<RecordSignedScore>,
+        home_score: u8,
+        away_score: u8,
+        quarter: u8,
+        is_final: bool,
+        timestamp: i64,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let clock = Clock::get()?;
+
+        require!(board.score_quorum == 1, SquaresError::QuorumRequired);
+
+        // The feed signature is checked by the Ed25519 instruction placed just before this one
+        let (feed, message) = load_ed25519_attestation(&ctx.accounts.instructions)?;
+        require!(
+            board.is_trusted_reporter(&ctx.accounts.oracle_registry, &feed),
+            SquaresError::Unauthorized
+        );
+        require!(
+            message == score_attestation_message(board.game_id, quarter, home_score, away_score, is_final, timestamp),
+            SquaresError::InvalidAttestation
+        );
+        require!(
+            (clock.unix_timestamp - timestamp).abs() <= MAX_ATTESTATION_AGE_SECS,
+            SquaresError::StaleAttestation
+        );
+        require!(timestamp > board.last_attestation_at, SquaresError::AttestationReplayed);
+        validate_score_report(board, home_score, away_score, quarter, is_final)?;
+        board.last_attestation_at = timestamp;
+
+        emit!(ScoreAttested {
+            board_id: board.game_id,
+            quarter,
+            feed,
+            timestamp,
+        });
+
+        apply_score(board, home_score, away_score, quarter, is_final, 1)
+    }
+
+    pub fn submit_score(
+        ctx: Context<SubmitScore>,
+        home_score: u8,
+        away_score: u8,
+        quarter: u8,
+        is_final: bool,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let tally = &mut ctx.accounts.score_tally;
+        let reporter = ctx.accounts.authority.key();
+
+        validate_score_report(board, home_score, away_score, quarter, is_final)?;
+
+        if tally.board == Pubkey::default() {
+            tally.board = board.key();
+            tally.quarter = quarter;
+            tally.votes = Vec::new();
+            tally.official = false;
+            tally.disagreements = 0;
+            tally.bump = ctx.bumps.score_tally;
+        }
+        require!(
+            tally.votes.iter().all(|vote| vote.reporter != reporter),
+            SquaresError::DuplicateScoreVote
+        );
+
+        // Any earlier vote for a different score is a disagreement worth surfacing
+        if let Some(conflict) = tally
+            .votes
+            .iter()
+            .find(|vote| !vote.matches(home_score, away_score, is_final))
+            .copied()
+        {
+            tally.disagreements += 1;
+            emit!(ScoreDisagreement {
+                board_id: board.game_id,
+                quarter,
+                reporter,
+                home_score,
+                away_score,
+                conflicting_reporter: conflict.reporter,
+                conflicting_home_score: conflict.home_score,
+                conflicting_away_score: conflict.away_score,
+            });
+        }
+
+        tally.votes.push(ScoreVote {
+            reporter,
+            home_score,
+            away_score,
+            is_final,
+        });
+        let agreeing = tally
+            .votes
+            .iter()
+            .filter(|vote| vote.matches(home_score, away_score, is_final))
+            .count() as u8;
+
+        emit!(ScoreVoteSubmitted {
+            board_id: board.game_id,
+            quarter,
+            reporter,
+            home_score,
+            away_score,
+            agreeing_votes: agreeing,
+            quorum: board.score_quorum,
+        });
+
+        if !tally.official && agreeing >= board.score_quorum {
+            tally.official = true;
+            apply_score(board, home_score, away_score, quarter, is_final, agreeing)?;
+        }
+        Ok(())
+    }
+
+    pub fn correct_score(
+        ctx: Context<CorrectScore>,
+        quarter: u8,
+        home_score: u8,
+        away_score: u8,
+        reason: CorrectionReason,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let tally = &mut ctx.accounts.correction_tally;
+        let reporter = ctx.accounts.authority.key();
+        let clock = Clock::get()?;
+
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        require!(quarter >= 1 && quarter <= board.sport_rules.periods, SquaresError::InvalidQuarter);
+        // The arbiter rules on disputed scores; reporters wait for that ruling
+        require!(board.open_disputes == 0, SquaresError::DisputeOpen);
+        let index = quarter as usize - 1;
+        let previous = board.periods[index];
+        require!(previous.scored, SquaresError::PeriodNotScored);
+        require!(!previous.settled, SquaresError::AlreadySettled);
+
+        // The revised score must still fit between its neighbouring periods
+        let (earlier_home, earlier_away) = board.score_before(quarter);
+        let max_points = board.sport_rules.max_points_per_period;
+        check_score_progression(earlier_home, earlier_away, home_score, away_score, max_points)?;
+        if let Some(next) = board.periods.get(index + 1).filter(|next| next.scored) {
+            check_score_progression(home_score, away_score, next.home_score, next.away_score, max_points)?;
+        }
+
+        // A correction needs the same quorum as the score it replaces
+        if tally.board == Pubkey::default() {
+            tally.board = board.key();
+            tally.quarter = quarter;
+            tally.votes = Vec::new();
+            tally.official = false;
+            tally.disagreements = 0;
+            tally.bump = ctx.bumps.correction_tally;
+        }
+        require!(
+            tally.votes.iter().all(|vote| vote.reporter != reporter),
+            SquaresError::DuplicateScoreVote
+        );
+        tally.votes.push(ScoreVote {
+            reporter,
+            home_score,
+            away_score,
+            is_final: false,
+        });
+        let agreeing = tally
+            .votes
+            .iter()
+            .filter(|vote| vote.matches(home_score, away_score, false))
+            .count() as u8;
+
+        emit!(ScoreCorrectionProposed {
+            board_id: board.game_id,
+            quarter,
+            reporter,
+            home_score,
+            away_score,
+            agreeing_votes: agreeing,
+            quorum: board.score_quorum,
+        });
+
+        if agreeing < board.score_quorum {
+            msg!("Q{} correction on board #{} has {}/{} votes", quarter, board.game_id, agreeing, board.score_quorum);
+            return Ok(());
+        }
+        // The next correction of this period starts a fresh vote
+        tally.votes.clear();
+
+        let period = &mut board.periods[index];
+        period.home_score = home_score;
+        period.away_score = away_score;
+        if quarter == board.quarter {
+            board.home_score = home_score;
+            board.away_score = away_score;
+        }
+        // Square owners get a full window to dispute the corrected result
+        if board.game_ended() {
+            board.final_scored_at = clock.unix_timestamp;
+        }
+
+        emit!(ScoreCorrected {
+            board_id: board.game_id,
+            quarter,
+            previous_home_score: previous.home_score,
+            previous_away_score: previous.away_score,
+            home_score,
+            away_score,
+            reason,
+            corrected_by: reporter,
+        });
+
+        msg!("Q{} score corrected to {}:{} for board #{}", quarter, home_score, away_score, board.game_id);
+        Ok(())
+    }
+
+    pub fn settle_winner(ctx: Context<SettleWinner>, quarter: u8) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        
+        let clock = Clock::get()?;
+
+        require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+        require!(quarter >= 1 && quarter <= board.sport_rules.periods, SquaresError::InvalidQuarter);
+        let index = quarter as usize - 1;
+        require!(board.periods[index].scored, SquaresError::GameNotEnded);
+        // The last regulation period may still be followed by overtime
+        require!(index + 1 < board.sport_rules.periods as usize || board.game_ended(), SquaresError::GameNotEnded);
+        // Boards with a challenge period settle only once it has passed undisputed
+        if board.dispute_window_secs > 0 {
+            require!(
+                board.game_ended()
+                    && clock.unix_timestamp >= board.final_scored_at + board.dispute_window_secs,
+                SquaresError::DisputeWindowOpen
+            );
+            require!(board.open_disputes == 0, SquaresError::DisputeOpen);
+        }
+        require!(!board.periods[index].settled, SquaresError::AlreadySettled);
+        // In order, so a rolled-forward share always lands on an unsettled period
+        require!(
+            board.periods[..index].iter().all(|period| period.settled),
+            SquaresError::PreviousPeriodUnsettled
+        );
+
+        // Find the winner based on the period's score digits
+        let winner_square = find_winner_square(
+            board.grid_size,
+            board.sport_rules.scoring_digit,
+            &board.home_headers,
+            &board.away_headers,
+            board.periods[index].home_score,
+            board.periods[index].away_score,
+        )?;
+
+        let payout_amount = board.period_share(index) + board.periods[index].rollover_in;
+        let mut winner_address = board.squares[winner_square as usize];
+        let mut paid_square = winner_square;
+        let mut claimants = 0;
+
+        let outcome = if winner_address != Pubkey::default() {
+            SettlementOutcome::Winner
+        } else {
+            let is_final = index + 1 == board.sport_rules.periods as usize;
+            match board.unsold_winner_policy {
+                UnsoldWinnerPolicy::RollForward if !is_final => {
+                    board.periods[index + 1].rollover_in += payout_amount;
+                    SettlementOutcome::RolledForward
+                }
+                UnsoldWinnerPolicy::RollForward | UnsoldWinnerPolicy::NearestSold => {
+                    paid_square = nearest_sold_square(&board.squares, board.grid_size, winner_square)
+                        .ok_or(SquaresError::NoWinner)?;
+                    winner_address = board.squares[paid_square as usize];
+                    SettlementOutcome::NearestSold
+                }
+                UnsoldWinnerPolicy::SplitAmongOwners => {
+                    claimants = board.distinct_owners();
+                    SettlementOutcome::SplitAmongOwners
+                }
+                UnsoldWinnerPolicy::RefundProRata => {
+                    claimants = board.squares_sold();
+                    SettlementOutcome::RefundProRata
+                }
+            }
+        };
+        if matches!(
+            outcome,
+            SettlementOutcome::SplitAmongOwners | SettlementOutcome::RefundProRata
+        ) {
+            require!(claimants > 0, SquaresError::NoWinner);
+        }
+        // A co-owned winner is paid out to each co-owner in turn
+        if matches!(outcome, SettlementOutcome::Winner | SettlementOutcome::NearestSold)
+            && board.is_shared(paid_square)
+        {
+            let shares = shared_square(board, paid_square, ctx.accounts.winner_shares.as_deref())?;
+            claimants = shares.co_owners.len() as u8;
+        }
+
+        // A rolled-forward share is paid by the next period instead
+        let payout_amount = if outcome == SettlementOutcome::RolledForward {
+            0
+        } else {
+            payout_amount
+        };
+        let period = &mut board.periods[index];
+        period.settled = true;
+        period.outcome = outcome;
+        period.winner = winner_address;
+        period.square_index = paid_square;
+        period.claimants = claimants;
+        period.payout_amount = payout_amount;
+        if board.active_periods().iter().all(|period| period.settled) {
+            board.transition(BoardStatus::Settled)?;
+            // Nothing may be left to pay, e.g. when every share was zero
+            board.mark_paid_if_complete()?;
+        }
+
+        emit!(WinnerSettled {
+            board_id: board.game_id,
+            quarter,
+            winner: winner_address,
+            payout_amount,
+            square_index: paid_square,
+            outcome,
+            unsold_winner_policy: board.unsold_winner_policy,
+        });
+
+        msg!("Q{} winner settled for board #{}: {} wins {} lamports",
+             quarter, board.game_id, winner_address, payout_amount);
+        Ok(())
+    }
+
+    pub fn payout_winner(ctx: Context<PayoutWinner>, quarter: u8) -> Result<()> {
+        let payout_amount;
+        let game_id;
+        let winner_key = *ctx.accounts.winner.key;
+        let mut co_owner_claim = None;
+        
+        {
+            let board = &ctx.accounts.board;
+            require!(quarter >= 1 && quarter <= board.sport_rules.periods, SquaresError::InvalidQuarter);
+            let period = &board.periods[quarter as usize - 1];
+            require!(period.settled, SquaresError::NoWinner);
+            require!(!period.paid && period.payout_amount > 0, SquaresError::NoPayout);
+            // Each co-owner of a shared square claims their weighted part separately
+            if board.is_shared(period.square_index) {
+                let shares = shared_square(board, period.square_index, ctx.accounts.winner_shares.as_deref())?;
+                let position = shares.position_of(&winner_key).ok_or(SquaresError::InvalidWinner)?;
+                require!(
+                    period.claimed_squares & (1u128 << position) == 0,
+                    SquaresError::NoPayout
+                );
+                co_owner_claim = Some((position, shares.share_of(position, period.payout_amount)));
+            } else if board.is_tokenized(period.square_index) {
+                require_square_holder(
+                    board,
+                    period.square_index,
+                    &winner_key,
+                    ctx.accounts.winner_token_account.as_deref(),
+                )
+                .map_err(|_| error!(SquaresError::InvalidWinner))?;
+            } else {
+                require!(period.winner == winner_key, SquaresError::InvalidWinner);
+            }
+            payout_amount = co_owner_claim.map_or(period.payout_amount, |(_, share)| share);
+            game_id = board.game_id;
+        }
+
+        // Transfer winnings to winner
+        **ctx.accounts.board.to_account_info().try_borrow_mut_lamports()? -= payout_amount;
+        **ctx.accounts.winner.to_account_info().try_borrow_mut_lamports()? += payout_amount;
+
+        // Mark as paid once every co-owner, if any, has claimed
+        let board = &mut ctx.accounts.board;
+        let period = &mut board.periods[quarter as usize - 1];
+        if let Some((position, _)) = co_owner_claim {
+            period.claimed_squares |= 1u128 << position;
+            period.paid = period.claimed_squares.count_ones() == period.claimants as u32;
+        } else {
+            period.paid = true;
+        }
+        board.mark_paid_if_complete()?;
+
+        emit!(WinnerPaid {
+            board_id: game_id,
+            quarter,
+            winner: winner_key,
+            amount: payout_amount,
+        });
+
+        msg!("Winner paid for board #{}", game_id);
+        Ok(())
+    }
+
+    pub fn claim_period_share(ctx: Context<ClaimPeriodShare>, quarter: u8, square_index: u8) -> Result<()> {
+        let claimant_key = ctx.accounts.claimant.key();
+        let amount;
+        let game_id;
+        let mut co_owner_claim = None;
+
+        {
+            let board = &ctx.accounts.board;
+            require!(quarter >= 1 && quarter <= board.sport_rules.periods, SquaresError::InvalidQuarter);
+            require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+            let period = &board.periods[quarter as usize - 1];
+            require!(
+                matches!(
+                    period.outcome,
+                    SettlementOutcome::SplitAmongOwners | SettlementOutcome::RefundProRata
+                ),
+                SquaresError::NoPayout
+            );
+            if !board.is_shared(square_index) {
+                require_square_holder(
+                    board,
+                    square_index,
+                    &claimant_key,
+                    ctx.accounts.claimant_token_account.as_deref(),
+                )?;
+            }
+            // Owner splits are claimed once per buying wallet, keyed by its first
+            // square; whoever holds that square now collects the share
+            if period.outcome == SettlementOutcome::SplitAmongOwners {
+                require!(
+                    board.first_square_of(&board.squares[square_index as usize]) == Some(square_index),
+                    SquaresError::InvalidSquareIndex
+                );
+            }
+            require!(
+                period.claimed_squares & (1u128 << square_index) == 0,
+                SquaresError::NoPayout
+            );
+
+            // Dust from the integer division goes to the lowest sold square
+            let per_claim = period.payout_amount / period.claimants as u64;
+            let dust = period.payout_amount % period.claimants as u64;
+            let square_amount = if board.first_sold_square() == Some(square_index) {
+                per_claim + dust
+            } else {
+                per_claim
+            };
+
+            // A shared square's part is split again by weight among its co-owners
+            if board.is_shared(square_index) {
+                let shares = shared_square(board, square_index, ctx.accounts.claimant_shares.as_deref())?;
+                let position = shares.position_of(&claimant_key).ok_or(SquaresError::NotSquareOwner)?;
+                let claimed = shares.period_shares_claimed[quarter as usize - 1];
+                require!(claimed & (1 << position) == 0, SquaresError::NoPayout);
+                co_owner_claim = Some(claimed | (1 << position));
+                amount = shares.share_of(position, square_amount);
+            } else {
+                amount = square_amount;
+            }
+            game_id = board.game_id;
+        }
+
+        **ctx.accounts.board.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx.accounts.claimant.to_account_info().try_borrow_mut_lamports()? += amount;
+
+        // A shared square's claim is complete once every co-owner has claimed
+        let mut claimed_in_full = true;
+        if let Some(claimed) = co_owner_claim {
+            let shares = ctx.accounts.claimant_shares.as_deref_mut().unwrap();
+            shares.period_shares_claimed[quarter as usize - 1] = claimed;
+            claimed_in_full = shares.all_claimed(claimed);
+        }
+
+        let board = &mut ctx.accounts.board;
+        if claimed_in_full {
+            board.periods[quarter as usize - 1].claimed_squares |= 1u128 << square_index;
+        }
+        board.mark_paid_if_complete()?;
+
+        emit!(PeriodShareClaimed {
+            board_id: game_id,
+            quarter,
+            square_index,
+            claimant: claimant_key,
+            amount,
+        });
+
+        msg!("Q{} share for square {} claimed on board #{}", quarter, square_index, game_id);
+        Ok(())
+    }
+
+    pub fn open_dispute(
+        ctx: Context<OpenDispute>,
+        quarter: u8,
+        home_score: u8,
+        away_score: u8,
+        square_index: u8,
+    ) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let clock = Clock::get()?;
+        let disputer = ctx.accounts.disputer.key();
+
+        require!(board.dispute_window_secs > 0, SquaresError::DisputeWindowClosed);
+        require!(board.game_ended(), SquaresError::GameNotEnded);
+        require!(
+            clock.unix_timestamp < board.final_scored_at + board.dispute_window_secs,
+            SquaresError::DisputeWindowClosed
+        );
+        require!(quarter >= 1 && quarter <= board.sport_rules.periods, SquaresError::InvalidQuarter);
+        // Only someone holding a square on the board may dispute its scores
+        require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+        require_square_holder(board, square_index, &disputer, ctx.accounts.disputer_token_account.as_deref())?;
+        let period = board.periods[quarter as usize - 1];
+        require!(
+            period.home_score != home_score || period.away_score != away_score,
+            SquaresError::InvalidScore
+        );
+
+        // The bond sits in the dispute account until the arbiter rules
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.disputer.to_account_info(),
+                to: ctx.accounts.dispute.to_account_info(),
+            },
+        );
+        anchor_lang::system_program::transfer(transfer_ctx, board.dispute_bond)?;
+
+        let dispute = &mut ctx.accounts.dispute;
+        dispute.board = board.key();
+        dispute.quarter = quarter;
+        dispute.disputer = disputer;
+        dispute.bond = board.dispute_bond;
+        dispute.home_score = home_score;
+        dispute.away_score = away_score;
+        dispute.opened_at = clock.unix_timestamp;
+        dispute.bump = ctx.bumps.dispute;
+
+        board.open_disputes += 1;
+
+        emit!(ScoreDisputed {
+            board_id: board.game_id,
+            quarter,
+            disputer,
+            bond: dispute.bond,
+            reported_home_score: period.home_score,
+            reported_away_score: period.away_score,
+            proposed_home_score: home_score,
+            proposed_away_score: away_score,
+        });
+
+        msg!("Q{} score disputed on board #{}", quarter, board.game_id);
+        Ok(())
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, upheld: bool) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let dispute = &ctx.accounts.dispute;
+        let index = dispute.quarter as usize - 1;
+
+        if upheld {
+            // Correct the score; the bond goes back when the account closes
+            let period = &mut board.periods[index];
+            require!(!period.settled, SquaresError::AlreadySettled);
+            period.home_score = dispute.home_score;
+            period.away_score = dispute.away_score;
+            if board.quarter == dispute.quarter {
+                board.home_score = dispute.home_score;
+                board.away_score = dispute.away_score;
+            }
+        } else {
+            // A failed dispute forfeits the bond to the treasury
+            **ctx.accounts.dispute.to_account_info().try_borrow_mut_lamports()? -= dispute.bond;
+            **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += dispute.bond;
+            ctx.accounts.treasury.total_collected += dispute.bond;
+        }
+
+        board.open_disputes -= 1;
+
+        emit!(DisputeResolved {
+            board_id: board.game_id,
+            quarter: dispute.quarter,
+            disputer: dispute.disputer,
+            upheld,
+            bond: dispute.bond,
+            home_score: board.periods[index].home_score,
+            away_score: board.periods[index].away_score,
+        });
+
+        msg!("Q{} dispute on board #{} resolved (upheld: {})", dispute.quarter, board.game_id, upheld);
+        Ok(())
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.authority = *ctx.accounts.authority.key;
+        treasury.total_collected = 0;
+        treasury.bump = ctx.bumps.treasury;
+
+        emit!(TreasuryInitialized {
+            authority: *ctx.accounts.authority.key,
+        });
+
+        msg!("Treasury initialized!");
+        Ok(())
+    }
+
+    pub fn initialize_oracle_registry(ctx: Context<InitializeOracleRegistry>) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+        registry.admin = *ctx.accounts.admin.key;
+        registry.reporters = Vec::new();
+        registry.vrf_queue = Pubkey::default();
+        registry.bump = ctx.bumps.oracle_registry;
+
+        emit!(OracleRegistryInitialized {
+            admin: *ctx.accounts.admin.key,
+        });
+
+        msg!("Oracle registry initialized!");
+        Ok(())
+    }
+
+    pub fn add_score_reporter(ctx: Context<ManageOracleRegistry>, reporter: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+
+        require!(!registry.is_reporter(&reporter), SquaresError::ReporterAlreadyRegistered);
+        require!(
+            registry.reporters.len() < MAX_SCORE_REPORTERS,
+            SquaresError::TooManyReporters
+        );
+
+        registry.reporters.push(reporter);
+
+        emit!(ScoreReporterAdded { reporter });
+
+        msg!("Score reporter {} added", reporter);
+        Ok(())
+    }
+
+    pub fn set_vrf_queue(ctx: Context<ManageOracleRegistry>, queue: Pubkey) -> Result<()> {
+        ctx.accounts.oracle_registry.vrf_queue = queue;
+
+        emit!(VrfQueueSet { queue });
+
+        msg!("VRF queue set to {}", queue);
+        Ok(())
+    }
+
+    pub fn remove_score_reporter(ctx: Context<ManageOracleRegistry>, reporter: Pubkey) -> Result<()> {
+        let registry = &mut ctx.accounts.oracle_registry;
+
+        let position = registry
+            .reporters
+            .iter()
+            .position(|key| *key == reporter)
+            .ok_or(SquaresError::ReporterNotRegistered)?;
+        registry.reporters.swap_remove(position);
+
+        emit!(ScoreReporterRemoved { reporter });
+
+        msg!("Score reporter {} removed", reporter);
+        Ok(())
+    }
+
+    pub fn boost_board(ctx: Context<BoostBoard>, duration_days: u8) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+        let clock = Clock::get()?;
+
+        // Validate duration
+        require!(
+            duration_days == 1 || duration_days == 3 || duration_days == 7,
+            SquaresError::InvalidBoostDuration
+        );
+
+        // Calculate boost fee based on duration
+        let base_fee = match duration_days {
+            1 => 50_000_000,   // 0.05 SOL for 1 day
+            3 => 120_000_000,  // 0.12 SOL for 3 days  
+            7 => 250_000_000,  // 0.25 SOL for 7 days
+            _ => return Err(SquaresError::InvalidBoostDuration.into()),
+        };
+
+        // Transfer SOL from CBL to treasury
+        let transfer_ix = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.treasury.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_ix,
+        );
+        anchor_lang::system_program::transfer(cpi_ctx, base_fee)?;
+
+        // Update board boost data
+        board.boost_amount += base_fee;
+        board.boost_expires_at = clock.unix_timestamp + (duration_days as i64 * 86400);
+
+        // Update treasury
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected += base_fee;
+
+        emit!(BoardBoosted {
+            game_id: board.game_id,
+            boost_amount: base_fee,
+            expires_at: board.boost_expires_at,
+            booster: *ctx.accounts.authority.key,
+        });
+
+        msg!("Board #{} boosted for {} days with {} lamports!", 
+             board.game_id, duration_days, base_fee);
+        Ok(())
+    }
+
+    pub fn update_price(ctx: Context<UpdatePrice>, price_per_square: u64) -> Result<()> {
+        let board = &mut ctx.accounts.board;
+
+        validate_price(price_per_square)?;
+        // Every buyer on a board pays the same price
+        require!(board.squares_sold() == 0, SquaresError::PriceLocked);
+
+        let old_price = board.price_per_square;
+        board.price_per_square = price_per_square;
+
+        emit!(PriceUpdated {
+            board_id: board.game_id,
+            old_price,
+            new_price: price_per_square,
+        });
+
+        msg!("Board #{} price updated from {} to {}", board.game_id, old_price, price_per_square);
         Ok(())
     }
 
     pub fn update_fill_rate(ctx: Context<UpdateFillRate>, fill_rate: u8) -> Result<()> {
         let board = &mut ctx.accounts.board;
         
-        require!(fill_rate <= 100, SquaresError::InvalidScore);
+        require!(fill_rate <= 100, SquaresError::InvalidScore);
+        
+        board.fill_rate = fill_rate;
+        
+        msg!("Board #{} fill rate updated to {}%", board.game_id, fill_rate);
+        Ok(())
+    }
+}
+
+// Headers need randomness nobody involved in the board can choose. Real
+// clusters read Switchboard on-demand randomness, which its oracles prove
+// on-chain when they reveal; the proof-less mock_vrf is only compiled in for
+// local validators through the `localnet` feature.
+
+/// Rejects a VRF account that cannot serve `board` or whose result is already known.
+#[cfg(not(feature = "localnet"))]
+fn check_vrf_request(vrf: &AccountInfo, _board: &Pubkey, registry: &OracleRegistry, clock: &Clock) -> Result<()> {
+    let randomness = load_switchboard_randomness(vrf, registry)?;
+    // Committed to the previous slot's hash and not revealed yet
+    require!(randomness.seed_slot + 1 == clock.slot, SquaresError::InvalidVrfProof);
+    require!(randomness.get_value(clock).is_err(), SquaresError::InvalidVrfProof);
+    Ok(())
+}
+
+/// The result `vrf` produced for the request made at `requested_slot`.
+#[cfg(not(feature = "localnet"))]
+fn read_vrf_result(
+    vrf: &AccountInfo,
+    _board: &Pubkey,
+    requested_slot: u64,
+    registry: &OracleRegistry,
+    clock: &Clock,
+) -> Result<[u8; 32]> {
+    let randomness = load_switchboard_randomness(vrf, registry)?;
+    // Still the commitment made at request time, revealed in this slot
+    require!(randomness.seed_slot + 1 == requested_slot, SquaresError::InvalidVrfProof);
+    randomness
+        .get_value(clock)
+        .map_err(|_| error!(SquaresError::InvalidVrfProof))
+}
+
+#[cfg(not(feature = "localnet"))]
+fn load_switchboard_randomness<'a>(
+    vrf: &'a AccountInfo,
+    registry: &OracleRegistry,
+) -> Result<std::cell::Ref<'a, RandomnessAccountData>> {
+    require!(*vrf.owner == SWITCHBOARD_ON_DEMAND_PROGRAM_ID, SquaresError::InvalidVrfProof);
+    let randomness = RandomnessAccountData::parse(vrf.try_borrow_data()?)
+        .map_err(|_| error!(SquaresError::InvalidVrfProof))?;
+    // Only oracles on the queue the registry admin chose may serve boards
+    require!(
+        registry.vrf_queue != Pubkey::default() && randomness.queue == registry.vrf_queue,
+        SquaresError::InvalidVrfProof
+    );
+    Ok(randomness)
+}
+
+/// Rejects a VRF account that cannot serve `board`.
+#[cfg(feature = "localnet")]
+fn check_vrf_request(vrf: &AccountInfo, board: &Pubkey, registry: &OracleRegistry, _clock: &Clock) -> Result<()> {
+    load_mock_vrf(vrf, board, registry).map(|_| ())
+}
+
+/// The result `vrf` produced for the request made at `requested_slot`.
+#[cfg(feature = "localnet")]
+fn read_vrf_result(
+    vrf: &AccountInfo,
+    board: &Pubkey,
+    requested_slot: u64,
+    registry: &OracleRegistry,
+    _clock: &Clock,
+) -> Result<[u8; 32]> {
+    let state = load_mock_vrf(vrf, board, registry)?;
+    require!(state.fulfilled_slot > requested_slot, SquaresError::InvalidVrfProof);
+    Ok(state.result)
+}
+
+#[cfg(feature = "localnet")]
+fn load_mock_vrf(vrf: &AccountInfo, board: &Pubkey, registry: &OracleRegistry) -> Result<VrfState> {
+    require!(*vrf.owner == mock_vrf::ID, SquaresError::InvalidVrfProof);
+    let state = VrfState::try_deserialize(&mut &vrf.try_borrow_data()?[..])?;
+    require!(state.consumer == *board, SquaresError::InvalidVrfProof);
+    // The mock posts results without a proof, so its oracle must be a key the
+    // registry admin vouches for rather than whoever initialised the account
+    require!(registry.is_reporter(&state.oracle), SquaresError::Unauthorized);
+    Ok(state)
+}
+
+fn validate_score_report(board: &Board, home_score: u8, away_score: u8, quarter: u8, is_final: bool) -> Result<()> {
+    require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+    require!(board.is_randomized(), SquaresError::NotRandomized);
+    let rules = board.sport_rules;
+    let overtime_periods = match rules.overtime {
+        OvertimePolicy::NotPlayed => 0,
+        OvertimePolicy::RegulationScore | OvertimePolicy::IncludeOvertime => MAX_OVERTIME_PERIODS,
+    };
+    require!(
+        quarter >= 1 && quarter <= rules.periods + overtime_periods,
+        SquaresError::InvalidQuarter
+    );
+    require!(!board.game_ended(), SquaresError::GameEnded);
+    // Periods are reported once each, in order; revisions go through correct_score
+    require!(quarter == board.quarter + 1, SquaresError::QuarterOutOfOrder);
+    require!(!is_final || quarter >= rules.periods, SquaresError::PrematureFinal);
+    // Without overtime the last regulation period always ends the game
+    require!(
+        is_final || overtime_periods > 0 || quarter < rules.periods,
+        SquaresError::FinalRequired
+    );
+    check_score_progression(
+        board.home_score,
+        board.away_score,
+        home_score,
+        away_score,
+        rules.max_points_per_period,
+    )
+}
+
+// Scores are cumulative, so each team's total never drops and can only climb
+// by a plausible amount within one period.
+fn check_score_progression(
+    from_home: u8,
+    from_away: u8,
+    to_home: u8,
+    to_away: u8,
+    max_points: u8,
+) -> Result<()> {
+    require!(to_home >= from_home && to_away >= from_away, SquaresError::ScoreDecreased);
+    require!(
+        to_home - from_home <= max_points && to_away - from_away <= max_points,
+        SquaresError::ImplausibleScore
+    );
+    Ok(())
+}
+
+// Makes a reported score official for the board and its period.
+fn apply_score(
+    board: &mut Board,
+    home_score: u8,
+    away_score: u8,
+    quarter: u8,
+    is_final: bool,
+    reporters: u8,
+) -> Result<()> {
+    board.home_score = home_score;
+    board.away_score = away_score;
+    board.quarter = quarter;
+
+    // Overtime only feeds the payout when the host opted into it
+    let period_type = if quarter <= board.sport_rules.periods {
+        PeriodType::Regulation
+    } else {
+        PeriodType::Overtime
+    };
+    let final_period = board.sport_rules.periods as usize - 1;
+    let index = match period_type {
+        PeriodType::Regulation => Some(quarter as usize - 1),
+        PeriodType::Overtime if is_final && board.sport_rules.overtime == OvertimePolicy::IncludeOvertime => {
+            Some(final_period)
+        }
+        PeriodType::Overtime => None,
+    };
+    if let Some(index) = index {
+        let period = &mut board.periods[index];
+        require!(!period.settled, SquaresError::AlreadySettled);
+        period.home_score = home_score;
+        period.away_score = away_score;
+        period.scored = true;
+    }
+
+    if board.status == BoardStatus::Randomized {
+        board.transition(BoardStatus::Live)?;
+    }
+
+    if is_final {
+        board.transition(BoardStatus::Final)?;
+        board.final_scored_at = Clock::get()?.unix_timestamp;
+    }
+
+    emit!(ScoreRecorded {
+        board_id: board.game_id,
+        home_score,
+        away_score,
+        quarter,
+        period_type,
+        is_final,
+        reporters,
+    });
+
+    msg!("Score recorded: {}:{} Q{} for board #{}", home_score, away_score, quarter, board.game_id);
+    Ok(())
+}
+
+pub const SCORE_ATTESTATION_DOMAIN: &[u8] = b"squares:score_attestation";
+
+/// Bytes a score feed signs off-chain for `record_signed_score`:
+/// `domain || game_id || quarter || home_score || away_score || is_final || timestamp`,
+/// with integers little-endian and `is_final` as a 0/1 byte.
+pub fn score_attestation_message(
+    game_id: u64,
+    quarter: u8,
+    home_score: u8,
+    away_score: u8,
+    is_final: bool,
+    timestamp: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(SCORE_ATTESTATION_DOMAIN.len() + 20);
+    message.extend_from_slice(SCORE_ATTESTATION_DOMAIN);
+    message.extend_from_slice(&game_id.to_le_bytes());
+    message.extend_from_slice(&[quarter, home_score, away_score, is_final as u8]);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message
+}
+
+// Reads the signer and message from the Ed25519 verification instruction
+// immediately preceding the current one. The runtime has already checked the
+// signature; we only accept a single signature whose key and message live in
+// that instruction's own data.
+fn load_ed25519_attestation(instructions: &AccountInfo) -> Result<(Pubkey, Vec<u8>)> {
+    let current = load_current_index_checked(instructions)?;
+    require!(current > 0, SquaresError::InvalidAttestation);
+    let ix = load_instruction_at_checked(current as usize - 1, instructions)?;
+    require!(ix.program_id == ed25519_program::ID, SquaresError::InvalidAttestation);
+
+    let data = &ix.data;
+    require!(data.len() >= 16 && data[0] == 1, SquaresError::InvalidAttestation);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let (signature_ix, public_key_offset, public_key_ix) = (read_u16(4), read_u16(6), read_u16(8));
+    let (message_offset, message_size, message_ix) = (read_u16(10), read_u16(12), read_u16(14));
+    let this_ix = u16::MAX as usize;
+    require!(
+        signature_ix == this_ix && public_key_ix == this_ix && message_ix == this_ix,
+        SquaresError::InvalidAttestation
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(SquaresError::InvalidAttestation)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(SquaresError::InvalidAttestation)?;
+    Ok((Pubkey::try_from(public_key).unwrap(), message.to_vec()))
+}
+
+// Hash of `slot` from the SlotHashes sysvar, which only keeps the newest
+// SLOT_HASHES_LEN slots, so callers must read it within a few minutes.
+fn slot_hash_at(slot_hashes: &AccountInfo, slot: u64) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    let count = data
+        .get(..8)
+        .map(|len| u64::from_le_bytes(len.try_into().unwrap()) as usize)
+        .ok_or(SquaresError::InvalidSlotHashes)?;
+    data.get(8..8 + 40 * count)
+        .ok_or(SquaresError::InvalidSlotHashes)?
+        .chunks_exact(40)
+        .find(|entry| entry[..8] == slot.to_le_bytes())
+        .and_then(|entry| entry[8..].try_into().ok())
+        .ok_or_else(|| error!(SquaresError::InvalidSlotHashes))
+}
+
+// Checks the board is currently selling squares.
+fn validate_sales_open(board: &Board, now: i64) -> Result<()> {
+    require!(!board.is_refundable(now), SquaresError::BoardCancelled);
+    match board.header_mode {
+        HeaderMode::HeadersFirst => require!(board.is_randomized(), SquaresError::NotRandomized),
+        HeaderMode::SalesFirst => require!(board.status == BoardStatus::Open, SquaresError::SalesClosed),
+    }
+    require!(!board.game_started(), SquaresError::GameAlreadyStarted);
+    require!(now >= board.sales_open_at, SquaresError::SalesNotOpen);
+    require!(now < board.sales_close_at, SquaresError::SalesClosed);
+    Ok(())
+}
+
+// Adds `added` squares to the buyer's running total for this board, enforcing
+// the board's per-wallet limit without scanning the grid.
+fn count_wallet_squares(
+    board: &Account<Board>,
+    wallet_squares: &mut WalletSquares,
+    buyer: Pubkey,
+    bump: u8,
+    added: usize,
+) -> Result<()> {
+    if wallet_squares.board == Pubkey::default() {
+        wallet_squares.board = board.key();
+        wallet_squares.wallet = buyer;
+        wallet_squares.squares_owned = 0;
+        wallet_squares.bump = bump;
+    }
+    let owned = wallet_squares.squares_owned as usize + added;
+    require!(
+        board.max_squares_per_wallet == 0 || owned <= board.max_squares_per_wallet as usize,
+        SquaresError::WalletLimitReached
+    );
+    wallet_squares.squares_owned = owned as u8;
+    Ok(())
+}
+
+// Squares change hands only before kickoff, on a live board, by their current owner.
+fn validate_resale(
+    board: &Account<Board>,
+    square_index: u8,
+    owner: &Pubkey,
+    owner_token_account: Option<&InterfaceAccount<TokenAccount>>,
+    now: i64,
+) -> Result<()> {
+    require!(square_index < board.grid_size.square_count(), SquaresError::InvalidSquareIndex);
+    require!(board.status != BoardStatus::Cancelled, SquaresError::BoardCancelled);
+    require!(
+        !board.game_started() && now < board.kickoff_at,
+        SquaresError::GameAlreadyStarted
+    );
+    require!(!board.is_shared(square_index), SquaresError::SquareShared);
+    require_square_holder(board, square_index, owner, owner_token_account)
+}
+
+// A tokenized square belongs to whoever holds its NFT; Board.squares only
+// tracks the buyer and moves made through this program.
+fn require_square_holder(
+    board: &Account<Board>,
+    square_index: u8,
+    holder: &Pubkey,
+    token_account: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<()> {
+    if board.is_tokenized(square_index) {
+        let token_account = token_account.ok_or(SquaresError::SquareTokenized)?;
+        require!(
+            token_account.mint == square_mint_address(&board.key(), square_index)
+                && token_account.owner == *holder
+                && token_account.amount == 1,
+            SquaresError::NotSquareOwner
+        );
+    } else {
+        require!(board.squares[square_index as usize] == *holder, SquaresError::NotSquareOwner);
+    }
+    Ok(())
+}
+
+// The co-owner list for a shared square, checked against the board and square.
+fn shared_square<'a>(
+    board: &Account<Board>,
+    square_index: u8,
+    shares: Option<&'a SquareShares>,
+) -> Result<&'a SquareShares> {
+    let shares = shares.ok_or(SquaresError::CoOwnersRequired)?;
+    require!(
+        shares.board == board.key() && shares.square_index == square_index,
+        SquaresError::CoOwnersRequired
+    );
+    Ok(shares)
+}
+
+fn square_mint_address(board: &Pubkey, square_index: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"square_mint", board.as_ref(), &[square_index]], &crate::ID).0
+}
+
+// Moves a square's NFT between the accounts already checked by validate_resale
+// and the caller; `signer_seeds` is empty when `authority` signed the transaction.
+fn move_square_token<'info>(
+    square_mint: Option<&InterfaceAccount<'info, Mint>>,
+    from: Option<&InterfaceAccount<'info, TokenAccount>>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    token_program: &Program<'info, Token2022>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let square_mint = square_mint.ok_or(SquaresError::SquareTokenized)?;
+    let from = from.ok_or(SquaresError::SquareTokenized)?;
+    require_keys_eq!(square_mint.key(), from.mint, SquaresError::SquareTokenized);
+    require_keys_eq!(to.mint, from.mint, SquaresError::InvalidRecipient);
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: from.to_account_info(),
+                mint: square_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        1,
+        0,
+    )
+}
+
+// Mints a square's NFT to `owner`'s token account; the tokenized bit stops it
+// from ever being minted again.
+fn mint_square<'info>(
+    board: &mut Account<'info, Board>,
+    square_mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    token_program: &Program<'info, Token2022>,
+    owner: Pubkey,
+    square_index: u8,
+) -> Result<()> {
+    let game_id = board.game_id;
+    let board_seeds: &[&[&[u8]]] = &[&[b"board", &game_id.to_le_bytes(), &[board.bump]]];
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: square_mint.clone(),
+                to: token_account.clone(),
+                authority: board.to_account_info(),
+            },
+            board_seeds,
+        ),
+        1,
+    )?;
+    board.tokenized_squares |= 1u128 << square_index;
+
+    emit!(SquareMinted {
+        board_id: game_id,
+        square_index,
+        mint: square_mint.key(),
+        owner,
+    });
+    Ok(())
+}
+
+// Creates and mints the NFT for every square in a batch purchase. A batch
+// cannot name its accounts up front, so `remaining_accounts` carries a
+// (mint, buyer token account) pair per square, in `square_indices` order.
+fn mint_sold_squares<'info>(
+    board: &mut Account<'info, Board>,
+    buyer: &Signer<'info>,
+    token_program: &Program<'info, Token2022>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+    remaining_accounts: &[AccountInfo<'info>],
+    square_indices: &[u8],
+) -> Result<()> {
+    require!(
+        remaining_accounts.len() == 2 * square_indices.len(),
+        SquaresError::InvalidSquareMint
+    );
+
+    let board_key = board.key();
+    let mint_space = anchor_spl::token_2022::spl_token_2022::state::Mint::LEN;
+    let mint_rent = Rent::get()?.minimum_balance(mint_space);
+    for (&square_index, pair) in square_indices.iter().zip(remaining_accounts.chunks(2)) {
+        let (square_mint, token_account) = (&pair[0], &pair[1]);
+        let (expected_mint, mint_bump) = Pubkey::find_program_address(
+            &[b"square_mint", board_key.as_ref(), &[square_index]],
+            &crate::ID,
+        );
+        require_keys_eq!(square_mint.key(), expected_mint, SquaresError::InvalidSquareMint);
+
+        // Allocate the way Anchor's `init` does, so lamports sent to the mint
+        // address ahead of time cannot block the purchase
+        let mint_seeds: &[&[&[u8]]] = &[&[b"square_mint", board_key.as_ref(), &[square_index], &[mint_bump]]];
+        let funded = square_mint.lamports();
+        if funded == 0 {
+            anchor_lang::system_program::create_account(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::CreateAccount {
+                        from: buyer.to_account_info(),
+                        to: square_mint.clone(),
+                    },
+                    mint_seeds,
+                ),
+                mint_rent,
+                mint_space as u64,
+                token_program.key,
+            )?;
+        } else {
+            if funded < mint_rent {
+                anchor_lang::system_program::transfer(
+                    CpiContext::new(
+                        system_program.to_account_info(),
+                        anchor_lang::system_program::Transfer {
+                            from: buyer.to_account_info(),
+                            to: square_mint.clone(),
+                        },
+                    ),
+                    mint_rent - funded,
+                )?;
+            }
+            anchor_lang::system_program::allocate(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Allocate {
+                        account_to_allocate: square_mint.clone(),
+                    },
+                    mint_seeds,
+                ),
+                mint_space as u64,
+            )?;
+            anchor_lang::system_program::assign(
+                CpiContext::new_with_signer(
+                    system_program.to_account_info(),
+                    anchor_lang::system_program::Assign {
+                        account_to_assign: square_mint.clone(),
+                    },
+                    mint_seeds,
+                ),
+                token_program.key,
+            )?;
+        }
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                token_program.to_account_info(),
+                InitializeMint2 {
+                    mint: square_mint.clone(),
+                },
+            ),
+            0,
+            &board_key,
+            Some(&board_key),
+        )?;
+
+        // The associated token program checks the address itself
+        associated_token::create_idempotent(CpiContext::new(
+            associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: buyer.to_account_info(),
+                associated_token: token_account.clone(),
+                authority: buyer.to_account_info(),
+                mint: square_mint.clone(),
+                system_program: system_program.to_account_info(),
+                token_program: token_program.to_account_info(),
+            },
+        ))?;
+
+        mint_square(board, square_mint, token_account, token_program, buyer.key(), square_index)?;
+    }
+    Ok(())
+}
+
+// Reassigns a square and moves it between the two wallets' counters.
+fn move_square(
+    board: &mut Account<Board>,
+    from_wallet_squares: &mut WalletSquares,
+    to_wallet_squares: &mut Account<WalletSquares>,
+    to_bump: u8,
+    square_index: u8,
+    to: Pubkey,
+) -> Result<()> {
+    count_wallet_squares(board, to_wallet_squares, to, to_bump, 1)?;
+    // A token bought outside the program was never counted for its new holder
+    from_wallet_squares.squares_owned = from_wallet_squares.squares_owned.saturating_sub(1);
+    board.squares[square_index as usize] = to;
+    Ok(())
+}
+
+// Takes payment for `square_indices` in one transfer and hands them to `buyer`.
+// Callers have already checked that every square is free.
+fn sell_squares<'info>(
+    board: &mut Account<'info, Board>,
+    buyer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    square_indices: Vec<u8>,
+) -> Result<()> {
+    let total = board.price_per_square * square_indices.len() as u64;
+    let transfer_ctx = CpiContext::new(
+        system_program.to_account_info(),
+        anchor_lang::system_program::Transfer {
+            from: buyer.to_account_info(),
+            to: board.to_account_info(),
+        },
+    );
+    anchor_lang::system_program::transfer(transfer_ctx, total)?;
+
+    record_sale(board, buyer.key, square_indices, total)
+}
+
+// Hands paid-for squares to `buyer` and adds their price to the pot.
+fn record_sale(board: &mut Board, buyer: &Pubkey, square_indices: Vec<u8>, total: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    for &square_index in &square_indices {
+        assign_square(board, buyer, square_index, clock.slot);
+    }
+    board.total_pot += total;
+
+    msg!("{} squares purchased for board #{}", square_indices.len(), board.game_id);
+    emit!(SquaresPurchased {
+        board_id: board.game_id,
+        buyer: *buyer,
+        square_indices,
+        price_per_square: board.price_per_square,
+        total_amount: total,
+    });
+    Ok(())
+}
+
+// Gives a paid-for square to `buyer` and folds the purchase into the board's entropy.
+fn assign_square(board: &mut Board, buyer: &Pubkey, square_index: u8, slot: u64) {
+    board.squares[square_index as usize] = *buyer;
+    board.entropy = hashv(&[&board.entropy, buyer.as_ref(), &[square_index], &slot.to_le_bytes()]).to_bytes();
+}
+
+fn validate_price(price_per_square: u64) -> Result<()> {
+    require!(
+        (MIN_PRICE_PER_SQUARE..=MAX_PRICE_PER_SQUARE).contains(&price_per_square),
+        SquaresError::InvalidPrice
+    );
+    Ok(())
+}
+
+pub const HOME_HEADER_DOMAIN: &[u8] = b"squares:home_headers";
+pub const AWAY_HEADER_DOMAIN: &[u8] = b"squares:away_headers";
+
+/// Derives a uniformly random permutation of the digits 0-9 from a 32-byte seed.
+///
+/// The seed is expanded into a byte stream `sha256(domain || seed || block)`
+/// for block = 0, 1, ... and consumed by a Fisher-Yates shuffle. Each swap
+/// index in `0..=i` is drawn by rejection sampling: bytes at or above the
+/// largest multiple of `i + 1` below 256 are discarded, so every index is
+/// equally likely. Using a distinct `domain` per axis makes the home and away
+/// headers independent even though they share the seed.
+pub fn derive_headers(seed: &[u8; 32], domain: &[u8]) -> [u8; 10] {
+    let mut headers = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let mut stream = SeedStream::new(seed, domain);
+
+    for i in (1..headers.len()).rev() {
+        let j = stream.next_below(i + 1);
+        headers.swap(i, j);
+    }
+
+    headers
+}
+
+pub const QUICK_PICK_DOMAIN: &[u8] = b"squares:quick_pick";
+
+/// Seed for `buyer`'s quick pick on `board`, drawn from the hash of the slot
+/// the pick was reserved in. Clients use it with `pick_squares` to learn which
+/// square mints to pass when filling the pick.
+pub fn quick_pick_seed(slot_hash: &[u8; 32], board: &Pubkey, buyer: &Pubkey) -> [u8; 32] {
+    hashv(&[slot_hash, board.as_ref(), buyer.as_ref()]).to_bytes()
+}
+
+/// Picks `count` distinct entries of `available` uniformly at random, using
+/// the same seed stream and rejection sampling as `derive_headers` to run a
+/// partial Fisher-Yates shuffle. `count` must not exceed `available.len()`.
+pub fn pick_squares(available: &[u8], count: usize, seed: &[u8; 32]) -> Vec<u8> {
+    let mut pool = available.to_vec();
+    let mut stream = SeedStream::new(seed, QUICK_PICK_DOMAIN);
+
+    for i in 0..count {
+        let j = i + stream.next_below(pool.len() - i);
+        pool.swap(i, j);
+    }
+
+    pool.truncate(count);
+    pool
+}
+
+// Byte stream `sha256(domain || seed || block)` for block = 0, 1, ...
+struct SeedStream<'a> {
+    seed: &'a [u8; 32],
+    domain: &'a [u8],
+    block_index: u32,
+    block: [u8; 32],
+    cursor: usize,
+}
+
+impl<'a> SeedStream<'a> {
+    fn new(seed: &'a [u8; 32], domain: &'a [u8]) -> Self {
+        let block = hashv(&[domain, seed, &0u32.to_le_bytes()]).to_bytes();
+        SeedStream { seed, domain, block_index: 0, block, cursor: 0 }
+    }
+
+    // Uniform index in `0..bound` (bound <= 256), rejecting bytes that would bias it.
+    fn next_below(&mut self, bound: usize) -> usize {
+        let bound = bound as u16;
+        let limit = 256 - (256 % bound);
+        loop {
+            if self.cursor == self.block.len() {
+                self.block_index += 1;
+                self.block = hashv(&[self.domain, self.seed, &self.block_index.to_le_bytes()]).to_bytes();
+                self.cursor = 0;
+            }
+            let byte = self.block[self.cursor] as u16;
+            self.cursor += 1;
+            if byte < limit {
+                return (byte % bound) as usize;
+            }
+        }
+    }
+}
+
+// Closest sold square to `target` by grid (Manhattan) distance; ties go to the
+// lowest square index so every validator resolves the same neighbor.
+fn nearest_sold_square(squares: &[Pubkey; 100], grid_size: GridSize, target: u8) -> Option<u8> {
+    let side = grid_size.side() as usize;
+    let (target_row, target_col) = ((target as usize / side) as i16, (target as usize % side) as i16);
+    squares[..grid_size.square_count() as usize]
+        .iter()
+        .enumerate()
+        .filter(|(_, owner)| **owner != Pubkey::default())
+        .min_by_key(|(index, _)| {
+            let (row, col) = ((*index / side) as i16, (*index % side) as i16);
+            ((row - target_row).abs() + (col - target_col).abs(), *index)
+        })
+        .map(|(index, _)| index as u8)
+}
+
+/// The square whose row and column headers contain the scores' counting
+/// digits. Headers list all ten digits in drawn order; on a 5x5 grid each
+/// header covers a consecutive pair of them.
+pub fn find_winner_square(
+    grid_size: GridSize,
+    scoring_digit: ScoringDigit,
+    home_headers: &[u8; 10],
+    away_headers: &[u8; 10],
+    home_score: u8,
+    away_score: u8,
+) -> Result<u8> {
+    let (home_digit, away_digit) = (scoring_digit.of(home_score), scoring_digit.of(away_score));
+    let home_index = home_headers.iter().position(|&x| x == home_digit)
+        .ok_or(SquaresError::InvalidScore)?;
+    let away_index = away_headers.iter().position(|&x| x == away_digit)
+        .ok_or(SquaresError::InvalidScore)?;
+
+    let digits = grid_size.digits_per_header() as usize;
+    Ok(((home_index / digits) * grid_size.side() as usize + away_index / digits) as u8)
+}
+
+impl OracleRegistry {
+    pub fn is_reporter(&self, key: &Pubkey) -> bool {
+        self.reporters.contains(key)
+    }
+}
+
+impl Board {
+    /// Moves the board to `next`, rejecting transitions the lifecycle does not allow.
+    pub fn transition(&mut self, next: BoardStatus) -> Result<()> {
+        require!(self.status.can_transition_to(next), SquaresError::InvalidStatusTransition);
+        emit!(BoardStatusChanged {
+            board_id: self.game_id,
+            from: self.status,
+            to: next,
+        });
+        self.status = next;
+        Ok(())
+    }
+
+    pub fn is_shared(&self, square_index: u8) -> bool {
+        self.shared_squares & (1u128 << square_index) != 0
+    }
+
+    pub fn is_tokenized(&self, square_index: u8) -> bool {
+        self.tokenized_squares & (1u128 << square_index) != 0
+    }
+
+    pub fn is_held(&self, square_index: u8) -> bool {
+        self.held_squares & (1u128 << square_index) != 0
+    }
+
+    /// Unsold squares nobody is holding, in index order.
+    pub fn available_squares(&self) -> Vec<u8> {
+        (0..self.grid_size.square_count())
+            .filter(|&index| self.squares[index as usize] == Pubkey::default() && !self.is_held(index))
+            .collect()
+    }
+
+    pub fn is_randomized(&self) -> bool {
+        matches!(
+            self.status,
+            BoardStatus::Randomized | BoardStatus::Live | BoardStatus::Final | BoardStatus::Settled | BoardStatus::Paid
+        )
+    }
+
+    pub fn game_started(&self) -> bool {
+        matches!(
+            self.status,
+            BoardStatus::Live | BoardStatus::Final | BoardStatus::Settled | BoardStatus::Paid
+        )
+    }
+
+    /// The periods this board's sport actually plays; any others stay unused.
+    pub fn active_periods(&self) -> &[PeriodResult] {
+        &self.periods[..self.sport_rules.periods as usize]
+    }
+
+    pub fn game_ended(&self) -> bool {
+        matches!(self.status, BoardStatus::Final | BoardStatus::Settled | BoardStatus::Paid)
+    }
+
+    fn mark_paid_if_complete(&mut self) -> Result<()> {
+        if self.status == BoardStatus::Settled && self.active_periods().iter().all(PeriodResult::is_paid_out) {
+            self.transition(BoardStatus::Paid)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `signer` may post scores, settle, or drive randomization for this board.
+    pub fn is_trusted_reporter(&self, registry: &OracleRegistry, signer: &Pubkey) -> bool {
+        registry.is_reporter(signer) || (self.host_can_report && *signer == self.authority)
+    }
+
+    /// Cumulative score at the end of the period before `quarter` (0-0 for the first).
+    pub fn score_before(&self, quarter: u8) -> (u8, u8) {
+        match quarter {
+            0 | 1 => (0, 0),
+            _ => {
+                let previous = &self.periods[quarter as usize - 2];
+                (previous.home_score, previous.away_score)
+            }
+        }
+    }
+
+    pub fn squares_sold(&self) -> u8 {
+        self.squares.iter().filter(|owner| **owner != Pubkey::default()).count() as u8
+    }
+
+    /// Number of different wallets holding at least one square.
+    pub fn distinct_owners(&self) -> u8 {
+        self.squares
+            .iter()
+            .enumerate()
+            .filter(|(index, owner)| {
+                **owner != Pubkey::default() && self.first_square_of(owner) == Some(*index as u8)
+            })
+            .count() as u8
+    }
+
+    /// Lowest square index held by `owner`, if any.
+    pub fn first_square_of(&self, owner: &Pubkey) -> Option<u8> {
+        self.squares.iter().position(|square| square == owner).map(|index| index as u8)
+    }
+
+    /// Lowest square index that has been sold, if any.
+    pub fn first_sold_square(&self) -> Option<u8> {
+        self.squares
+            .iter()
+            .position(|owner| *owner != Pubkey::default())
+            .map(|index| index as u8)
+    }
+
+    /// Lamports paid out for a period. Rounding dust from the earlier periods
+    /// goes to the final period so the whole pot is always distributed.
+    pub fn period_share(&self, index: usize) -> u64 {
+        let share = |period: &PeriodResult| {
+            (self.total_pot as u128 * period.split_bps as u128 / BPS_DENOMINATOR as u128) as u64
+        };
+        if index + 1 == self.sport_rules.periods as usize {
+            self.total_pot - self.periods[..index].iter().map(share).sum::<u64>()
+        } else {
+            share(&self.periods[index])
+        }
+    }
+
+    /// Cancelled boards, and commit-reveal boards whose host never revealed,
+    /// return each buyer's purchase price through claim_refund.
+    pub fn is_refundable(&self, current_timestamp: i64) -> bool {
+        if self.status == BoardStatus::Cancelled {
+            return true;
+        }
+        match self.randomness_mode {
+            RandomnessMode::CommitReveal { reveal_deadline, .. } => {
+                !self.is_randomized() && current_timestamp > reveal_deadline
+            }
+            RandomnessMode::Vrf => false,
+        }
+    }
+}
+
+// Board Boost utility functions
+impl Board {
+    pub fn is_boosted(&self, current_timestamp: i64) -> bool {
+        self.boost_expires_at > current_timestamp && self.boost_amount > 0
+    }
+    
+    pub fn calculate_boost_score(&self, current_timestamp: i64) -> f64 {
+        if !self.is_boosted(current_timestamp) {
+            return 0.0;
+        }
+        
+        // Normalize boost amount (scale relative to baseline 0.25 SOL)
+        let normalized_boost = (self.boost_amount as f64 / 250_000_000.0).min(1.0);
         
-        board.fill_rate = fill_rate;
+        // Weight by fill rate, time remaining, and boost amount
+        let fill_rate_factor = self.fill_rate as f64 / 100.0;
+        let time_remaining = (self.boost_expires_at - current_timestamp) as f64;
+        let urgency_factor = if time_remaining > 0.0 { 
+            (time_remaining / 86400.0).min(7.0) / 7.0 // Normalize to 0-1 based on days remaining
+        } else { 
+            0.0 
+        };
         
-        msg!("Board #{} fill rate updated to {}%", board.game_id, fill_rate);
-        Ok(())
+        // Weighted scoring algorithm
+        normalized_boost * 0.5 + fill_rate_factor * 0.3 + urgency_factor * 0.2
     }
 }
 
-// Helper function to derive headers from randomness
-fn derive_headers(randomness: &[u8]) -> [u8; 10] {
-    let mut headers = [0u8; 10];
-    let mut used = [false; 10];
-    
-    for i in 0..10 {
-        let mut value = (randomness[i % randomness.len()] as usize) % 10;
-        while used[value] {
-            value = (value + 1) % 10;
-        }
-        headers[i] = value as u8;
-        used[value] = true;
-    }
-    
-    headers
+#[derive(Accounts)]
+#[instruction(game_id: u64)]
+pub struct CreateBoard<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 8 + 32 + 1 + 8 + 1 + 1 + 1 + (74 * PERIODS) + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 1 + 16 + 2 + 16 + 16 + 1 + 1 + 4 + 8 + 8 + 32 + 8 + 1 + 8 + 1 + (32 * 100) + 10 + 10 + 1 + 32 + 8 + 8 + 32 + (1 + 32 + 8) + 1 + 32 + 16 + 8 + 8 + 8 + 1 + 8 + 1 + 32,
+        seeds = [b"board", game_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub board: Box<Account<'info, Board>>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomization<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    /// CHECK: Validated against the build's randomness source by check_vrf_request
+    pub vrf_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillVrf<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    /// CHECK: Bound at request time; read through read_vrf_result
+    #[account(address = board.vrf_account @ SquaresError::InvalidVrfProof)]
+    pub vrf_account: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseSales<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    pub closer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordClosingSlotHash<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    /// CHECK: Address-checked sysvar, parsed by slot_hash_at
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSeed<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+    )]
+    pub board: Box<Account<'info, Board>>,
+    pub authority: Signer<'info>,
+    /// CHECK: Address-checked SlotHashes sysvar, read raw because it is too large to deserialize
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelBoard<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    pub canceller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Required when the square was minted as an NFT
+    pub owner_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Required when the square has co-owners
+    #[account(mut)]
+    pub owner_shares: Option<Account<'info, SquareShares>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(square_index: u8)]
+pub struct PurchaseSquare<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = board,
+        mint::freeze_authority = board,
+        mint::token_program = token_program,
+        seeds = [b"square_mint", board.key().as_ref(), &[square_index]],
+        bump
+    )]
+    pub square_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = square_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub square_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
-// Helper function to find winner square
-fn find_winner_square(
-    home_headers: &[u8; 10],
-    away_headers: &[u8; 10],
-    home_digit: u8,
-    away_digit: u8,
-) -> Result<u8> {
-    let home_index = home_headers.iter().position(|&x| x == home_digit)
-        .ok_or(SquaresError::InvalidScore)?;
-    let away_index = away_headers.iter().position(|&x| x == away_digit)
-        .ok_or(SquaresError::InvalidScore)?;
-    
-    Ok((home_index * 10 + away_index) as u8)
+#[derive(Accounts)]
+pub struct PurchaseSquares<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: a (square mint, buyer token account) pair per square
 }
 
-// Board Boost utility functions
-impl Board {
-    pub fn is_boosted(&self, current_timestamp: i64) -> bool {
-        self.boost_expires_at > current_timestamp && self.boost_amount > 0
-    }
-    
-    pub fn calculate_boost_score(&self, current_timestamp: i64) -> f64 {
-        if !self.is_boosted(current_timestamp) {
-            return 0.0;
-        }
-        
-        // Normalize boost amount (scale relative to baseline 0.25 SOL)
-        let normalized_boost = (self.boost_amount as f64 / 250_000_000.0).min(1.0);
-        
-        // Weight by fill rate, time remaining, and boost amount
-        let fill_rate_factor = self.fill_rate as f64 / 100.0;
-        let time_remaining = (self.boost_expires_at - current_timestamp) as f64;
-        let urgency_factor = if time_remaining > 0.0 { 
-            (time_remaining / 86400.0).min(7.0) / 7.0 // Normalize to 0-1 based on days remaining
-        } else { 
-            0.0 
-        };
-        
-        // Weighted scoring algorithm
-        normalized_boost * 0.5 + fill_rate_factor * 0.3 + urgency_factor * 0.2
-    }
+#[derive(Accounts)]
+#[instruction(square_index: u8)]
+pub struct PurchaseSharedSquare<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(
+        init,
+        payer = buyer,
+        space = 8 + 32 + 1 + 4 + (34 * MAX_CO_OWNERS) + 1 + PERIODS + 1,
+        seeds = [b"square_shares", board.key().as_ref(), &[square_index]],
+        bump
+    )]
+    pub square_shares: Account<'info, SquareShares>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        mint::decimals = 0,
+        mint::authority = board,
+        mint::freeze_authority = board,
+        mint::token_program = token_program,
+        seeds = [b"square_mint", board.key().as_ref(), &[square_index]],
+        bump
+    )]
+    pub square_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = square_mint,
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program,
+    )]
+    pub square_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(game_id: u64)]
-pub struct CreateBoard<'info> {
+pub struct QuickPick<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
     #[account(
         init,
-        payer = authority,
-        space = 8 + 8 + 32 + 1 + 1 + 1 + 1 + 32 + 8 + 8 + 1 + 1 + 1 + (32 * 100) + (1 * 10) + (1 * 10) + 1 + 8 + 8 + 8 + 1 + 8 + 1 + 32,
-        seeds = [b"board", game_id.to_le_bytes().as_ref()],
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 8 + 8 + 1,
+        seeds = [b"quick_pick", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub ticket: Account<'info, QuickPickTicket>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FillQuickPick<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"quick_pick", board.key().as_ref(), buyer.key().as_ref()],
+        bump = ticket.bump,
+        has_one = buyer,
+        close = buyer,
+    )]
+    pub ticket: Account<'info, QuickPickTicket>,
+    #[account(
+        mut,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump = wallet_squares.bump,
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// CHECK: Address-checked sysvar, parsed by slot_hash_at
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    // Remaining accounts: a (square mint, buyer token account) pair per picked square
+}
+
+#[derive(Accounts)]
+pub struct ReleaseQuickPick<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"quick_pick", board.key().as_ref(), buyer.key().as_ref()],
+        bump = ticket.bump,
+        has_one = buyer,
+        close = buyer,
+    )]
+    pub ticket: Account<'info, QuickPickTicket>,
+    #[account(
+        mut,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump = wallet_squares.bump,
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    /// CHECK: Receives the rent and any refunded deposit; matched against the ticket by has_one
+    #[account(mut)]
+    pub buyer: UncheckedAccount<'info>,
+    pub releaser: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseWalletSquares<'info> {
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"wallet_squares", board.key().as_ref(), wallet.key().as_ref()],
+        bump = wallet_squares.bump,
+        has_one = board,
+        has_one = wallet,
+        close = wallet,
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(square_index: u8)]
+pub struct HoldSquare<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init,
+        payer = holder,
+        space = 8 + 32 + 1 + 32 + 8 + 8 + 1,
+        seeds = [b"hold", board.key().as_ref(), &[square_index]],
         bump
     )]
-    pub board: Account<'info, Board>,
+    pub hold: Account<'info, SquareHold>,
     #[account(mut)]
+    pub holder: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseHeldSquare<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"hold", board.key().as_ref(), &[hold.square_index]],
+        bump = hold.bump,
+        has_one = holder,
+        close = holder,
+    )]
+    pub hold: Account<'info, SquareHold>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), holder.key().as_ref()],
+        bump
+    )]
+    pub wallet_squares: Account<'info, WalletSquares>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        mint::decimals = 0,
+        mint::authority = board,
+        mint::freeze_authority = board,
+        mint::token_program = token_program,
+        seeds = [b"square_mint", board.key().as_ref(), &[hold.square_index]],
+        bump
+    )]
+    pub square_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = holder,
+        associated_token::mint = square_mint,
+        associated_token::authority = holder,
+        associated_token::token_program = token_program,
+    )]
+    pub square_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub holder: Signer<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseHold<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"hold", board.key().as_ref(), &[hold.square_index]],
+        bump = hold.bump,
+        has_one = holder,
+        close = holder,
+    )]
+    pub hold: Account<'info, SquareHold>,
+    /// CHECK: Receives the rent and any refunded deposit; matched against the hold by has_one
+    #[account(mut)]
+    pub holder: UncheckedAccount<'info>,
+    pub releaser: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(square_index: u8, recipient: Pubkey)]
+pub struct TransferSquare<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub owner_wallet_squares: Account<'info, WalletSquares>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub recipient_wallet_squares: Account<'info, WalletSquares>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    /// Required when the square was minted as an NFT
+    pub square_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub owner_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(square_index: u8)]
+pub struct ListSquare<'info> {
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + 32 + 1 + 32 + 8 + 1,
+        seeds = [b"listing", board.key().as_ref(), &[square_index]],
+        bump
+    )]
+    pub listing: Account<'info, SquareListing>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+    /// Required when the square was minted as an NFT
+    #[account(mut)]
+    pub seller_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelListing<'info> {
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"listing", board.key().as_ref(), &[listing.square_index]],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller,
+    )]
+    pub listing: Account<'info, SquareListing>,
+    /// CHECK: Gets the listing's rent back; matched against the listing by has_one
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    /// The seller, or the square's current owner when the listing is stale
+    pub canceller: Signer<'info>,
+    /// The seller's NFT account, to revoke the listing's delegation, or the
+    /// current owner's, to show the square has moved on
+    #[account(mut)]
+    pub token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct FillListing<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"listing", board.key().as_ref(), &[listing.square_index]],
+        bump = listing.bump,
+        has_one = seller,
+        close = seller,
+    )]
+    pub listing: Account<'info, SquareListing>,
+    /// CHECK: Receives the sale proceeds; matched against the listing by has_one
+    #[account(mut)]
+    pub seller: UncheckedAccount<'info>,
+    /// CHECK: Receives the royalty; must be the board's host
+    #[account(mut, address = board.authority)]
+    pub host: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub seller_wallet_squares: Account<'info, WalletSquares>,
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + 32 + 32 + 1 + 1,
+        seeds = [b"wallet_squares", board.key().as_ref(), buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_wallet_squares: Account<'info, WalletSquares>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    /// Required when the square was minted as an NFT
+    pub square_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut)]
+    pub seller_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(mut)]
+    pub buyer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordScore<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RecordSignedScore<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    /// CHECK: Address-checked instructions sysvar
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(home_score: u8, away_score: u8, quarter: u8)]
+pub struct SubmitScore<'info> {
+    #[account(mut)]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1 + (4 + 35 * (MAX_SCORE_REPORTERS + 1)) + 1 + 1 + 1,
+        seeds = [b"score_tally", board.key().as_ref(), &[quarter]],
+        bump
+    )]
+    pub score_tally: Account<'info, ScoreTally>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        mut,
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
     pub authority: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct RequestRandomization<'info> {
+#[instruction(quarter: u8)]
+pub struct CorrectScore<'info> {
     #[account(mut)]
-    pub board: Account<'info, Board>,
-    /// CHECK: VRF account will be verified by Switchboard
-    pub vrf_account: UncheckedAccount<'info>,
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + 32 + 1 + (4 + 35 * (MAX_SCORE_REPORTERS + 1)) + 1 + 1 + 1,
+        seeds = [b"correction_tally", board.key().as_ref(), &[quarter]],
+        bump
+    )]
+    pub correction_tally: Account<'info, ScoreTally>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        mut,
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
     pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct FulfillVrf<'info> {
+pub struct SettleWinner<'info> {
     #[account(mut)]
-    pub board: Account<'info, Board>,
-    /// CHECK: Switchboard VRF account
-    pub vrf_account: AccountInfo<'info>,
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        constraint = board.is_trusted_reporter(&oracle_registry, authority.key) @ SquaresError::Unauthorized
+    )]
     pub authority: Signer<'info>,
+    /// Required when the winning square has co-owners
+    pub winner_shares: Option<Account<'info, SquareShares>>,
 }
 
 #[derive(Accounts)]
-pub struct PurchaseSquare<'info> {
+pub struct PayoutWinner<'info> {
     #[account(mut)]
-    pub board: Account<'info, Board>,
+    pub board: Box<Account<'info, Board>>,
     #[account(mut)]
-    pub buyer: Signer<'info>,
+    pub winner: Signer<'info>,
+    /// Required when the winning square was minted as an NFT
+    pub winner_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Required when the winning square has co-owners
+    pub winner_shares: Option<Account<'info, SquareShares>>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct RecordScore<'info> {
+pub struct ClaimPeriodShare<'info> {
     #[account(mut)]
-    pub board: Account<'info, Board>,
-    pub authority: Signer<'info>,
+    pub board: Box<Account<'info, Board>>,
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    /// Required when the square was minted as an NFT
+    pub claimant_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Required when the square has co-owners
+    #[account(mut)]
+    pub claimant_shares: Option<Account<'info, SquareShares>>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct SettleWinner<'info> {
+#[instruction(quarter: u8)]
+pub struct OpenDispute<'info> {
     #[account(mut)]
-    pub board: Account<'info, Board>,
-    pub authority: Signer<'info>,
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        init,
+        payer = disputer,
+        space = 8 + 32 + 1 + 32 + 8 + 1 + 1 + 8 + 1,
+        seeds = [b"dispute", board.key().as_ref(), &[quarter]],
+        bump
+    )]
+    pub dispute: Account<'info, ScoreDispute>,
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+    /// Required when the disputer's square was minted as an NFT
+    pub disputer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct PayoutWinner<'info> {
-    #[account(mut)]
-    pub board: Account<'info, Board>,
+pub struct ResolveDispute<'info> {
+    #[account(
+        mut,
+        has_one = arbiter,
+    )]
+    pub board: Box<Account<'info, Board>>,
+    #[account(
+        mut,
+        seeds = [b"dispute", board.key().as_ref(), &[dispute.quarter]],
+        bump = dispute.bump,
+        has_one = disputer,
+        close = disputer,
+    )]
+    pub dispute: Account<'info, ScoreDispute>,
+    /// CHECK: Receives the bond or rent back; matched against the dispute by has_one
     #[account(mut)]
-    pub winner: Signer<'info>,
-    pub system_program: Program<'info, System>,
+    pub disputer: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub arbiter: Signer<'info>,
 }
 
 #[derive(Accounts)]
@@ -450,13 +3653,49 @@ pub struct InitializeTreasury<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeOracleRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + (4 + 32 * MAX_SCORE_REPORTERS) + 32 + 1,
+        seeds = [b"oracle_registry"],
+        bump
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()) @ SquaresError::Unauthorized
+    )]
+    pub program: Program<'info, crate::program::Squares>,
+    // Only the upgrade authority may claim the registry, so nobody can front-run it
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ SquaresError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageOracleRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"oracle_registry"],
+        bump = oracle_registry.bump,
+        has_one = admin,
+    )]
+    pub oracle_registry: Account<'info, OracleRegistry>,
+    pub admin: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct BoostBoard<'info> {
     #[account(
         mut,
         has_one = authority,
     )]
-    pub board: Account<'info, Board>,
+    pub board: Box<Account<'info, Board>>,
     #[account(mut)]
     pub authority: Signer<'info>,
     #[account(
@@ -468,13 +3707,23 @@ pub struct BoostBoard<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdatePrice<'info> {
+    #[account(
+        mut,
+        has_one = authority,
+    )]
+    pub board: Box<Account<'info, Board>>,
+    pub authority: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateFillRate<'info> {
     #[account(
         mut,
         has_one = authority,
     )]
-    pub board: Account<'info, Board>,
+    pub board: Box<Account<'info, Board>>,
     pub authority: Signer<'info>,
 }
 
@@ -482,20 +3731,47 @@ pub struct UpdateFillRate<'info> {
 pub struct Board {
     pub game_id: u64,
     pub authority: Pubkey,
-    pub finalized: bool,
-    pub randomized: bool,
-    pub game_started: bool,
-    pub game_ended: bool,
-    pub winner: Pubkey,
-    pub payout_amount: u64,
+    pub status: BoardStatus,
     pub total_pot: u64,
     pub home_score: u8,
     pub away_score: u8,
     pub quarter: u8,
-    pub squares: [Pubkey; 100],
-    pub home_headers: [u8; 10],
+    pub periods: [PeriodResult; PERIODS],
+    pub unsold_winner_policy: UnsoldWinnerPolicy,
+    pub settle_deadline: i64,
+    pub sales_open_at: i64,
+    pub sales_close_at: i64,
+    pub kickoff_at: i64,
+    pub max_squares_per_wallet: u8,
+    pub hold_duration_secs: i64,
+    pub hold_deposit: u64,
+    pub expired_hold_policy: ExpiredHoldPolicy,
+    pub held_squares: u128,       // Bitmap of squares with an outstanding hold
+    pub resale_royalty_bps: u16,
+    pub tokenized_squares: u128,  // Bitmap of squares minted as Token-2022 NFTs
+    pub shared_squares: u128,     // Bitmap of squares bought with co-owners
+    pub host_can_report: bool,
+    pub score_quorum: u8,
+    pub sport_rules: SportRules,
+    pub dispute_window_secs: i64,
+    pub dispute_bond: u64,
+    pub arbiter: Pubkey,
+    pub final_scored_at: i64,     // When the final score was recorded; starts the dispute window
+    pub open_disputes: u8,
+    pub last_attestation_at: i64, // Timestamp of the newest signed score; older ones are replays
+    pub grid_size: GridSize,
+    pub squares: [Pubkey; 100],   // Only the first grid_size.square_count() are used
+    pub home_headers: [u8; 10],   // Digit order; a 5x5 row header is each consecutive pair
     pub away_headers: [u8; 10],
     pub bump: u8,
+    pub vrf_account: Pubkey,      // VRF account bound by request_randomization
+    pub vrf_requested_slot: u64,  // Slot randomization was requested at
+    pub sales_closed_slot: u64,   // Slot sales closed in; its hash salts a revealed seed
+    pub sales_closed_slot_hash: [u8; 32], // Recorded before it leaves SlotHashes; zero until then
+    pub randomness_mode: RandomnessMode,
+    pub header_mode: HeaderMode,
+    pub entropy: [u8; 32],        // Running hash of purchases, mixed into revealed seeds
+    pub refunded_squares: u128,   // Bitmap of squares whose purchase has been refunded
     
     // Board Boost fields
     pub boost_amount: u64,        // Total SOL paid for boosts
@@ -514,6 +3790,123 @@ pub struct Treasury {
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ScoreVote {
+    pub reporter: Pubkey,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub is_final: bool,
+}
+
+impl ScoreVote {
+    pub fn matches(&self, home_score: u8, away_score: u8, is_final: bool) -> bool {
+        self.home_score == home_score && self.away_score == away_score && self.is_final == is_final
+    }
+}
+
+#[account]
+pub struct ScoreTally {
+    pub board: Pubkey,
+    pub quarter: u8,
+    pub votes: Vec<ScoreVote>,    // One per reporter: the registry's MAX_SCORE_REPORTERS plus the host
+    pub official: bool,           // Quorum reached and score applied to the board
+    pub disagreements: u8,        // Votes that conflicted with an earlier vote
+    pub bump: u8,
+}
+
+#[account]
+pub struct SquareHold {
+    pub board: Pubkey,
+    pub square_index: u8,
+    pub holder: Pubkey,
+    pub deposit: u64,
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct SquareShares {
+    pub board: Pubkey,
+    pub square_index: u8,
+    pub co_owners: Vec<CoOwner>,  // Up to MAX_CO_OWNERS
+    pub refunds_claimed: u8,      // Bitmap of co-owner positions that claimed their refund part
+    pub period_shares_claimed: [u8; PERIODS], // Per period, co-owners that claimed their split part
+    pub bump: u8,
+}
+
+impl SquareShares {
+    /// Position of `owner` among the co-owners, if listed.
+    pub fn position_of(&self, owner: &Pubkey) -> Option<usize> {
+        self.co_owners.iter().position(|co_owner| co_owner.owner == *owner)
+    }
+
+    /// Whether every co-owner is marked in `claimed`, a bitmap of positions.
+    pub fn all_claimed(&self, claimed: u8) -> bool {
+        claimed.count_ones() == self.co_owners.len() as u32
+    }
+
+    /// The part of `amount` owed to the co-owner at `position`, by weight. Rounding
+    /// dust goes to the first listed co-owner so the parts always sum to `amount`.
+    pub fn share_of(&self, position: usize, amount: u64) -> u64 {
+        let total_weight: u128 = self.co_owners.iter().map(|co_owner| co_owner.weight as u128).sum();
+        let part = |weight: u16| (amount as u128 * weight as u128 / total_weight) as u64;
+        let share = part(self.co_owners[position].weight);
+        if position == 0 {
+            let distributed: u64 = self.co_owners.iter().map(|co_owner| part(co_owner.weight)).sum();
+            share + amount - distributed
+        } else {
+            share
+        }
+    }
+}
+
+#[account]
+pub struct SquareListing {
+    pub board: Pubkey,
+    pub square_index: u8,
+    pub seller: Pubkey,
+    pub ask: u64,                 // Lamports the buyer pays, royalty included
+    pub bump: u8,
+}
+
+#[account]
+pub struct WalletSquares {
+    pub board: Pubkey,
+    pub wallet: Pubkey,
+    pub squares_owned: u8,        // Squares this wallet has bought on the board
+    pub bump: u8,
+}
+
+#[account]
+pub struct QuickPickTicket {
+    pub board: Pubkey,
+    pub buyer: Pubkey,
+    pub count: u8,
+    pub price_per_square: u64,    // Locked in so the deposit covers every pick
+    pub reserved_slot: u64,       // Its SlotHashes entry seeds the draw
+    pub bump: u8,
+}
+
+#[account]
+pub struct ScoreDispute {
+    pub board: Pubkey,
+    pub quarter: u8,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    pub home_score: u8,           // Score the disputer claims is correct
+    pub away_score: u8,
+    pub opened_at: i64,
+    pub bump: u8,
+}
+
+#[account]
+pub struct OracleRegistry {
+    pub admin: Pubkey,
+    pub reporters: Vec<Pubkey>,   // Keys trusted to post scores, at most MAX_SCORE_REPORTERS
+    pub vrf_queue: Pubkey,        // Switchboard queue whose randomness boards accept
+    pub bump: u8,
+}
+
 #[event]
 pub struct BoardCreated {
     pub game_id: u64,
@@ -541,25 +3934,247 @@ pub struct SquarePurchased {
     pub amount: u64,
 }
 
+#[event]
+pub struct SquareMinted {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct SquareHeld {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub holder: Pubkey,
+    pub deposit: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct HoldReleased {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub holder: Pubkey,
+    pub released_by: Pubkey,
+    pub deposit: u64,
+    pub forfeited: bool,          // Deposit went to the pot instead of back to the holder
+}
+
+#[event]
+pub struct SquareShared {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub co_owners: Vec<CoOwner>,
+}
+
+#[event]
+pub struct SquareTransferred {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub price: u64,               // 0 for a gift
+    pub royalty: u64,             // Part of the price paid to the host
+}
+
+#[event]
+pub struct SquareListed {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub seller: Pubkey,
+    pub ask: u64,
+}
+
+#[event]
+pub struct ListingCancelled {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub seller: Pubkey,
+    pub cancelled_by: Pubkey,
+}
+
+#[event]
+pub struct SquaresPurchased {
+    pub board_id: u64,
+    pub buyer: Pubkey,
+    pub square_indices: Vec<u8>,  // In the order they were requested
+    pub price_per_square: u64,
+    pub total_amount: u64,
+}
+
+#[event]
+pub struct ClosingSlotHashRecorded {
+    pub board_id: u64,
+    pub sales_closed_slot: u64,
+    pub slot_hash: [u8; 32],
+}
+
+#[event]
+pub struct QuickPickReserved {
+    pub board_id: u64,
+    pub buyer: Pubkey,
+    pub count: u8,
+    pub reserved_slot: u64,
+}
+
+#[event]
+pub struct QuickPickReleased {
+    pub board_id: u64,
+    pub buyer: Pubkey,
+    pub released_by: Pubkey,
+    pub deposit: u64,
+    pub forfeited: bool,          // Deposit went to the pot instead of back to the buyer
+}
+
+#[event]
+pub struct SalesClosed {
+    pub board_id: u64,
+    pub squares_sold: u8,
+}
+
+#[event]
+pub struct BoardStatusChanged {
+    pub board_id: u64,
+    pub from: BoardStatus,
+    pub to: BoardStatus,
+}
+
+#[event]
+pub struct BoardCancelled {
+    pub board_id: u64,
+    pub cancelled_by: Pubkey,
+    pub reason: CancelReason,
+    pub total_pot: u64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub board_id: u64,
+    pub square_index: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PriceUpdated {
+    pub board_id: u64,
+    pub old_price: u64,
+    pub new_price: u64,
+}
+
 #[event]
 pub struct ScoreRecorded {
     pub board_id: u64,
     pub home_score: u8,
     pub away_score: u8,
     pub quarter: u8,
+    pub period_type: PeriodType,
+    pub is_final: bool,           // Game over; no more periods follow
+    pub reporters: u8,            // Reporters that agreed on this score
+}
+
+#[event]
+pub struct ScoreVoteSubmitted {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub reporter: Pubkey,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub agreeing_votes: u8,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct ScoreDisagreement {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub reporter: Pubkey,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub conflicting_reporter: Pubkey,
+    pub conflicting_home_score: u8,
+    pub conflicting_away_score: u8,
+}
+
+#[event]
+pub struct ScoreCorrected {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub previous_home_score: u8,
+    pub previous_away_score: u8,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub reason: CorrectionReason,
+    pub corrected_by: Pubkey,
+}
+
+#[event]
+pub struct ScoreCorrectionProposed {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub reporter: Pubkey,
+    pub home_score: u8,
+    pub away_score: u8,
+    pub agreeing_votes: u8,
+    pub quorum: u8,
+}
+
+#[event]
+pub struct ScoreAttested {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub feed: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ScoreDisputed {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    pub reported_home_score: u8,
+    pub reported_away_score: u8,
+    pub proposed_home_score: u8,
+    pub proposed_away_score: u8,
+}
+
+#[event]
+pub struct DisputeResolved {
+    pub board_id: u64,
+    pub quarter: u8,
+    pub disputer: Pubkey,
+    pub upheld: bool,
+    pub bond: u64,
+    pub home_score: u8,           // Official score after resolution
+    pub away_score: u8,
 }
 
 #[event]
 pub struct WinnerSettled {
     pub board_id: u64,
+    pub quarter: u8,
     pub winner: Pubkey,
     pub payout_amount: u64,
+    pub square_index: u8,          // Square actually paid; differs from the winning cell for NearestSold
+    pub outcome: SettlementOutcome,
+    pub unsold_winner_policy: UnsoldWinnerPolicy,
+}
+
+#[event]
+pub struct PeriodShareClaimed {
+    pub board_id: u64,
+    pub quarter: u8,
     pub square_index: u8,
+    pub claimant: Pubkey,
+    pub amount: u64,
 }
 
 #[event]
 pub struct WinnerPaid {
     pub board_id: u64,
+    pub quarter: u8,
     pub winner: Pubkey,
     pub amount: u64,
 }
@@ -569,6 +4184,26 @@ pub struct TreasuryInitialized {
     pub authority: Pubkey,
 }
 
+#[event]
+pub struct OracleRegistryInitialized {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct VrfQueueSet {
+    pub queue: Pubkey,
+}
+
+#[event]
+pub struct ScoreReporterAdded {
+    pub reporter: Pubkey,
+}
+
+#[event]
+pub struct ScoreReporterRemoved {
+    pub reporter: Pubkey,
+}
+
 #[event]
 pub struct BoardBoosted {
     pub game_id: u64,
@@ -609,4 +4244,128 @@ pub enum SquaresError {
     InvalidScore,
     #[msg("Invalid boost duration")]
     InvalidBoostDuration,
+    #[msg("Randomization has already been requested")]
+    RandomizationAlreadyRequested,
+    #[msg("Instruction does not match the board's randomness mode")]
+    WrongRandomnessMode,
+    #[msg("Reveal deadline must be after sales close")]
+    InvalidRevealDeadline,
+    #[msg("Reveal deadline has passed")]
+    RevealDeadlinePassed,
+    #[msg("Revealed seed does not match the commitment")]
+    InvalidSeedReveal,
+    #[msg("Square sales are closed")]
+    SalesClosed,
+    #[msg("Square sales are still open")]
+    SalesStillOpen,
+    #[msg("Board is not refundable")]
+    NotRefundable,
+    #[msg("Square has already been refunded")]
+    AlreadyRefunded,
+    #[msg("Signer does not own this square")]
+    NotSquareOwner,
+    #[msg("Instruction does not match the board's header mode")]
+    WrongHeaderMode,
+    #[msg("Price per square is outside the allowed range")]
+    InvalidPrice,
+    #[msg("Price cannot change after the first square is sold")]
+    PriceLocked,
+    #[msg("Payout split must add up to 10000 basis points")]
+    InvalidPayoutSplit,
+    #[msg("Earlier periods must be settled first")]
+    PreviousPeriodUnsettled,
+    #[msg("Board has been cancelled")]
+    BoardCancelled,
+    #[msg("Board cannot be cancelled yet")]
+    CannotCancel,
+    #[msg("Settle deadline must be in the future")]
+    InvalidSettleDeadline,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Score reporter is already registered")]
+    ReporterAlreadyRegistered,
+    #[msg("Score reporter is not registered")]
+    ReporterNotRegistered,
+    #[msg("Oracle registry is full")]
+    TooManyReporters,
+    #[msg("Score quorum must be between 1 and the registry capacity")]
+    InvalidQuorum,
+    #[msg("Board requires scores to reach quorum through submit_score")]
+    QuorumRequired,
+    #[msg("Reporter has already voted on this score")]
+    DuplicateScoreVote,
+    #[msg("Disputes need a positive bond and an arbiter")]
+    InvalidDisputeConfig,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Dispute window is closed")]
+    DisputeWindowClosed,
+    #[msg("A score dispute is awaiting the arbiter")]
+    DisputeOpen,
+    #[msg("Score attestation is missing or does not match")]
+    InvalidAttestation,
+    #[msg("Score attestation is too old or too far in the future")]
+    StaleAttestation,
+    #[msg("Score attestation has already been used")]
+    AttestationReplayed,
+    #[msg("Quarters must be reported in order")]
+    QuarterOutOfOrder,
+    #[msg("Scores cannot go down")]
+    ScoreDecreased,
+    #[msg("Score jump is too large for one period")]
+    ImplausibleScore,
+    #[msg("Period has not been scored")]
+    PeriodNotScored,
+    #[msg("Only the last regulation period or overtime can end the game")]
+    PrematureFinal,
+    #[msg("Board cannot move to that status from its current one")]
+    InvalidStatusTransition,
+    #[msg("Sales must open before they close, close in the future and end by kickoff")]
+    InvalidSalesWindow,
+    #[msg("Sales have not opened yet")]
+    SalesNotOpen,
+    #[msg("Square index listed more than once")]
+    DuplicateSquare,
+    #[msg("Fewer squares remain than requested")]
+    NotEnoughSquares,
+    #[msg("SlotHashes has no entry for that slot yet, or it has aged out")]
+    InvalidSlotHashes,
+    #[msg("Wallet has reached the board's square limit")]
+    WalletLimitReached,
+    #[msg("Holds need a positive deposit and a non-negative duration")]
+    InvalidHoldConfig,
+    #[msg("This board does not allow holds")]
+    HoldsDisabled,
+    #[msg("Square is held by another buyer")]
+    SquareHeld,
+    #[msg("Hold has expired")]
+    HoldExpired,
+    #[msg("Hold has not expired")]
+    HoldActive,
+    #[msg("Resale royalty cannot exceed 10000 bps")]
+    InvalidRoyalty,
+    #[msg("Squares cannot be transferred to that wallet")]
+    InvalidRecipient,
+    #[msg("Listing no longer matches the square's owner or the board's state")]
+    ListingStale,
+    #[msg("Square is held as a token; pass its token accounts")]
+    SquareTokenized,
+    #[msg("A shared square needs 2 to 8 distinct co-owners with non-zero weights")]
+    InvalidCoOwners,
+    #[msg("The winning square is shared; pass its co-owner account")]
+    CoOwnersRequired,
+    #[msg("Shared squares cannot be transferred")]
+    SquareShared,
+    #[msg("Sport rules need 1 to 4 periods and a non-zero points cap")]
+    InvalidSportRules,
+    #[msg("Boards without overtime must mark the last regulation period final")]
+    FinalRequired,
+    #[msg("Pass a square mint and buyer token account for every square bought")]
+    InvalidSquareMint,
+    #[msg("Board must be paid out or cancelled first")]
+    BoardStillActive,
+    #[msg("Quick pick can still be filled")]
+    QuickPickPending,
+    #[msg("The closing slot's hash is already recorded")]
+    ClosingSlotHashRecorded,
 }
\ No newline at end of file
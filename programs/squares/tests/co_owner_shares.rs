@@ -0,0 +1,58 @@
+//! Checks for `SquareShares`, which splits a shared square's winnings and refunds.
+
+use anchor_lang::prelude::Pubkey;
+use squares::{CoOwner, SquareShares};
+
+fn shares(weights: &[u16]) -> SquareShares {
+    SquareShares {
+        board: Pubkey::default(),
+        square_index: 0,
+        co_owners: weights
+            .iter()
+            .map(|&weight| CoOwner { owner: Pubkey::new_unique(), weight })
+            .collect(),
+        refunds_claimed: 0,
+        period_shares_claimed: [0; 4],
+        bump: 0,
+    }
+}
+
+#[test]
+fn parts_follow_the_weights() {
+    let shares = shares(&[1, 1, 2]);
+    assert_eq!(shares.share_of(0, 1_000), 250);
+    assert_eq!(shares.share_of(1, 1_000), 250);
+    assert_eq!(shares.share_of(2, 1_000), 500);
+}
+
+#[test]
+fn dust_goes_to_the_first_co_owner() {
+    let shares = shares(&[1, 1, 1]);
+    assert_eq!(shares.share_of(0, 100), 34);
+    assert_eq!(shares.share_of(1, 100), 33);
+    assert_eq!(shares.share_of(2, 100), 33);
+}
+
+#[test]
+fn parts_always_sum_to_the_amount() {
+    let weight_sets: [&[u16]; 4] = [&[3, 7], &[1, 2, 3, 4, 5, 6, 7, 8], &[u16::MAX, 1], &[9_999, 1, 13]];
+    for weights in weight_sets {
+        let shares = shares(weights);
+        for amount in [0, 1, 7, 99, 1_000_003, u64::MAX / 2] {
+            let total: u64 = (0..weights.len()).map(|position| shares.share_of(position, amount)).sum();
+            assert_eq!(total, amount, "weights {weights:?}, amount {amount}");
+        }
+    }
+}
+
+#[test]
+fn claims_complete_once_every_co_owner_has_claimed() {
+    let shares = shares(&[2, 1, 1]);
+    let second = shares.co_owners[1].owner;
+    assert_eq!(shares.position_of(&second), Some(1));
+    assert_eq!(shares.position_of(&Pubkey::new_unique()), None);
+
+    assert!(!shares.all_claimed(0b011));
+    assert!(!shares.all_claimed(0b101));
+    assert!(shares.all_claimed(0b111));
+}
@@ -0,0 +1,20 @@
+//! Chi-square helpers shared by the statistical test suites.
+
+// Upper-tail z-score for p = 1e-6.
+const Z_CRITICAL: f64 = 4.7534;
+
+/// Wilson-Hilferty approximation of the chi-square critical value.
+pub fn chi_square_critical(degrees_of_freedom: f64) -> f64 {
+    let k = 2.0 / (9.0 * degrees_of_freedom);
+    degrees_of_freedom * (1.0 - k + Z_CRITICAL * k.sqrt()).powi(3)
+}
+
+pub fn chi_square(observed: &[u64], expected: f64) -> f64 {
+    observed
+        .iter()
+        .map(|&count| {
+            let delta = count as f64 - expected;
+            delta * delta / expected
+        })
+        .sum()
+}
@@ -0,0 +1,51 @@
+//! Checks for `find_winner_square` on both grid sizes and scoring digits.
+
+use squares::{
+    derive_headers, find_winner_square, GridSize, ScoringDigit, AWAY_HEADER_DOMAIN, HOME_HEADER_DOMAIN,
+};
+
+const HOME: [u8; 10] = [3, 7, 0, 9, 1, 4, 8, 2, 6, 5];
+const AWAY: [u8; 10] = [6, 2, 8, 0, 5, 9, 1, 3, 7, 4];
+
+#[test]
+fn ten_by_ten_uses_one_digit_per_header() {
+    // Home 7 is row 1, away 5 is column 4
+    assert_eq!(find_winner_square(GridSize::TenByTen, ScoringDigit::Last, &HOME, &AWAY, 7, 5).unwrap(), 14);
+    assert_eq!(find_winner_square(GridSize::TenByTen, ScoringDigit::Last, &HOME, &AWAY, 5, 4).unwrap(), 99);
+}
+
+#[test]
+fn five_by_five_pairs_consecutive_digits() {
+    // Rows are {3,7} {0,9} {1,4} {8,2} {6,5}; columns are {6,2} {8,0} {5,9} {1,3} {7,4}
+    assert_eq!(find_winner_square(GridSize::FiveByFive, ScoringDigit::Last, &HOME, &AWAY, 3, 6).unwrap(), 0);
+    assert_eq!(find_winner_square(GridSize::FiveByFive, ScoringDigit::Last, &HOME, &AWAY, 7, 2).unwrap(), 0);
+    assert_eq!(find_winner_square(GridSize::FiveByFive, ScoringDigit::Last, &HOME, &AWAY, 9, 5).unwrap(), 7);
+    assert_eq!(find_winner_square(GridSize::FiveByFive, ScoringDigit::Last, &HOME, &AWAY, 5, 4).unwrap(), 24);
+}
+
+#[test]
+fn every_score_lands_on_exactly_one_cell() {
+    let home = derive_headers(&[7u8; 32], HOME_HEADER_DOMAIN);
+    let away = derive_headers(&[7u8; 32], AWAY_HEADER_DOMAIN);
+
+    for grid_size in [GridSize::TenByTen, GridSize::FiveByFive] {
+        let mut hits = vec![0u32; grid_size.square_count() as usize];
+        for home_digit in 0..10 {
+            for away_digit in 0..10 {
+                hits[find_winner_square(grid_size, ScoringDigit::Last, &home, &away, home_digit, away_digit).unwrap() as usize] += 1;
+            }
+        }
+        // Each cell covers digits_per_header² of the 100 digit pairs
+        let per_cell = (grid_size.digits_per_header() as u32).pow(2);
+        assert!(hits.iter().all(|&count| count == per_cell), "{grid_size:?}: {hits:?}");
+    }
+}
+
+#[test]
+fn scores_are_read_through_the_scoring_digit() {
+    // 27-45: last digits 7 and 5, tens digits 2 and 4
+    assert_eq!(find_winner_square(GridSize::TenByTen, ScoringDigit::Last, &HOME, &AWAY, 27, 45).unwrap(), 14);
+    assert_eq!(find_winner_square(GridSize::TenByTen, ScoringDigit::Tens, &HOME, &AWAY, 27, 45).unwrap(), 79);
+    // Scores past 99 wrap to the tens digit alone
+    assert_eq!(find_winner_square(GridSize::TenByTen, ScoringDigit::Tens, &HOME, &AWAY, 127, 145).unwrap(), 79);
+}
@@ -0,0 +1,106 @@
+//! Statistical checks for `derive_headers`.
+//!
+//! Each test derives headers from millions of distinct seeds and runs a
+//! chi-square goodness-of-fit test against the uniform distribution. Seeds are
+//! deterministic, so a failure is reproducible rather than flaky; the critical
+//! values correspond to a one-in-a-million false positive rate.
+
+mod common;
+
+use common::{chi_square, chi_square_critical};
+use squares::{derive_headers, AWAY_HEADER_DOMAIN, HOME_HEADER_DOMAIN};
+
+const SEEDS: u64 = 2_000_000;
+
+fn seed(n: u64) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&n.to_le_bytes());
+    seed[24..].copy_from_slice(&n.wrapping_mul(0x9E37_79B9_7F4A_7C15).to_le_bytes());
+    seed
+}
+
+#[test]
+fn headers_are_always_a_permutation() {
+    for n in 0..100_000 {
+        for domain in [HOME_HEADER_DOMAIN, AWAY_HEADER_DOMAIN] {
+            let mut headers = derive_headers(&seed(n), domain);
+            headers.sort_unstable();
+            assert_eq!(headers, [0, 1, 2, 3, 4, 5, 6, 7, 8, 9], "seed {n}");
+        }
+    }
+}
+
+#[test]
+fn each_digit_is_uniform_at_each_position() {
+    let mut home = [[0u64; 10]; 10];
+    let mut away = [[0u64; 10]; 10];
+
+    for n in 0..SEEDS {
+        let seed = seed(n);
+        for (position, digit) in derive_headers(&seed, HOME_HEADER_DOMAIN).iter().enumerate() {
+            home[position][*digit as usize] += 1;
+        }
+        for (position, digit) in derive_headers(&seed, AWAY_HEADER_DOMAIN).iter().enumerate() {
+            away[position][*digit as usize] += 1;
+        }
+    }
+
+    let expected = SEEDS as f64 / 10.0;
+    let critical = chi_square_critical(9.0);
+    for (axis, counts) in [("home", home), ("away", away)] {
+        for (position, observed) in counts.iter().enumerate() {
+            let statistic = chi_square(observed, expected);
+            assert!(
+                statistic < critical,
+                "{axis} position {position}: chi-square {statistic:.2} >= {critical:.2} ({observed:?})"
+            );
+        }
+    }
+}
+
+#[test]
+fn adjacent_positions_are_jointly_uniform() {
+    // Catches shuffles whose marginals look fine but whose digits are
+    // correlated, e.g. a biased swap that favours neighbouring values.
+    let mut pairs = [[0u64; 90]; 9];
+
+    for n in 0..SEEDS {
+        let headers = derive_headers(&seed(n), HOME_HEADER_DOMAIN);
+        for position in 0..9 {
+            let (a, b) = (headers[position] as usize, headers[position + 1] as usize);
+            // Index the 90 ordered pairs of distinct digits
+            let cell = a * 9 + if b > a { b - 1 } else { b };
+            pairs[position][cell] += 1;
+        }
+    }
+
+    let expected = SEEDS as f64 / 90.0;
+    let critical = chi_square_critical(89.0);
+    for (position, observed) in pairs.iter().enumerate() {
+        let statistic = chi_square(observed, expected);
+        assert!(
+            statistic < critical,
+            "positions {position}/{}: chi-square {statistic:.2} >= {critical:.2}",
+            position + 1
+        );
+    }
+}
+
+#[test]
+fn home_and_away_headers_are_independent() {
+    let mut joint = [0u64; 100];
+
+    for n in 0..SEEDS {
+        let seed = seed(n);
+        let home = derive_headers(&seed, HOME_HEADER_DOMAIN);
+        let away = derive_headers(&seed, AWAY_HEADER_DOMAIN);
+        joint[home[0] as usize * 10 + away[0] as usize] += 1;
+    }
+
+    let statistic = chi_square(&joint, SEEDS as f64 / 100.0);
+    let critical = chi_square_critical(81.0);
+    assert!(
+        statistic < critical,
+        "home/away first digit: chi-square {statistic:.2} >= {critical:.2}"
+    );
+}
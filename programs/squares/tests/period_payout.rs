@@ -0,0 +1,33 @@
+//! Checks for `PeriodResult::is_paid_out`, which gates a board reaching Paid.
+
+use squares::{PeriodResult, SettlementOutcome};
+
+fn settled(outcome: SettlementOutcome, payout_amount: u64, claimants: u8) -> PeriodResult {
+    PeriodResult { settled: true, outcome, payout_amount, claimants, ..Default::default() }
+}
+
+#[test]
+fn split_periods_wait_for_every_claim() {
+    for outcome in [SettlementOutcome::SplitAmongOwners, SettlementOutcome::RefundProRata] {
+        let mut period = settled(outcome, 1_000, 2);
+        assert!(!period.is_paid_out());
+
+        period.claimed_squares = 1 << 7;
+        assert!(!period.is_paid_out());
+
+        period.claimed_squares |= 1 << 42;
+        assert!(period.is_paid_out());
+    }
+}
+
+#[test]
+fn periods_without_a_payout_need_no_claims() {
+    for outcome in [
+        SettlementOutcome::Winner,
+        SettlementOutcome::NearestSold,
+        SettlementOutcome::SplitAmongOwners,
+        SettlementOutcome::RefundProRata,
+    ] {
+        assert!(settled(outcome, 0, 3).is_paid_out(), "{outcome:?}");
+    }
+}
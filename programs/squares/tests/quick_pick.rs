@@ -0,0 +1,70 @@
+//! Checks for `pick_squares`, which assigns quick-pick squares.
+
+mod common;
+
+use std::collections::HashSet;
+
+use anchor_lang::prelude::Pubkey;
+use common::{chi_square, chi_square_critical};
+use squares::{pick_squares, quick_pick_seed};
+
+const SEEDS: u64 = 200_000;
+
+fn seed(n: u64) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[..8].copy_from_slice(&n.to_le_bytes());
+    seed
+}
+
+#[test]
+fn picks_are_distinct_unsold_squares() {
+    let available: Vec<u8> = (0..100).filter(|index| index % 3 != 0).collect();
+
+    for n in 0..10_000 {
+        let count = (n % available.len() as u64) as usize + 1;
+        let picks = pick_squares(&available, count, &seed(n));
+        assert_eq!(picks.len(), count, "seed {n}");
+
+        let mut sorted = picks.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), count, "seed {n}: duplicate pick");
+        assert!(picks.iter().all(|pick| available.contains(pick)), "seed {n}");
+    }
+}
+
+#[test]
+fn every_available_square_is_equally_likely() {
+    let available: Vec<u8> = (0..100).filter(|index| index % 7 != 0).collect();
+    let mut counts = vec![0u64; available.len()];
+
+    for n in 0..SEEDS {
+        for pick in pick_squares(&available, 3, &seed(n)) {
+            counts[available.iter().position(|&index| index == pick).unwrap()] += 1;
+        }
+    }
+
+    let expected = (SEEDS * 3) as f64 / available.len() as f64;
+    let statistic = chi_square(&counts, expected);
+    let critical = chi_square_critical((available.len() - 1) as f64);
+    assert!(statistic < critical, "chi-square {statistic:.2} >= {critical:.2}");
+}
+
+#[test]
+fn same_buyer_draws_different_squares_under_different_slot_hashes() {
+    let board = Pubkey::new_unique();
+    let buyer = Pubkey::new_unique();
+    let available: Vec<u8> = (0..100).collect();
+    let draw = |slot_hash: &[u8; 32]| pick_squares(&available, 1, &quick_pick_seed(slot_hash, &board, &buyer))[0];
+
+    // Filling is reproducible once the reservation slot's hash is known...
+    assert_eq!(draw(&seed(1)), draw(&seed(1)));
+
+    // ...but before then the buyer cannot tell which square they will get
+    let squares: HashSet<u8> = (0..200).map(|n| draw(&seed(n))).collect();
+    assert!(squares.len() > 50, "only {} distinct squares", squares.len());
+    assert_ne!(
+        quick_pick_seed(&seed(1), &board, &buyer),
+        quick_pick_seed(&seed(1), &Pubkey::new_unique(), &buyer)
+    );
+}
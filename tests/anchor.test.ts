@@ -4,6 +4,15 @@ import { Program } from '@coral-xyz/anchor';
 import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
 import { expect } from 'chai';
 import { Squares } from '../target/types/squares';
+import {
+  defaultBoardConfig,
+  initializeOracleRegistry,
+  quickPicks,
+  slotHashAt,
+  squareTokenAccount as squareTokenAccountOf,
+  squareTokenAccounts,
+} from './helpers';
+import { MockVrf } from '../target/types/mock_vrf';
 
 describe('Football Squares Program', () => {
   // Configure the client to use the local cluster
@@ -18,6 +27,17 @@ describe('Football Squares Program', () => {
   let player1: Keypair;
   let player2: Keypair;
 
+  const FINAL_QUARTER = 4;
+  const squareTokenAccount = (owner: PublicKey, squareIndex: number) =>
+    squareTokenAccountOf(program.programId, boardPda, owner, squareIndex);
+
+  const boardConfig = defaultBoardConfig({
+    payoutSplitBps: [1000, 2000, 2000, 5000],
+    settleDeadline: new anchor.BN(Math.floor(Date.now() / 1000) + 7 * 86400),
+    salesCloseAt: new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+    kickoffAt: new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+  });
+
   before(async () => {
     // Generate test accounts
     authority = Keypair.generate();
@@ -45,6 +65,13 @@ describe('Football Squares Program', () => {
       ),
     );
 
+    // The registry is a program-wide singleton; another suite may have created it
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+
     // Calculate board PDA
     [boardPda] = PublicKey.findProgramAddressSync(
       [
@@ -58,7 +85,12 @@ describe('Football Squares Program', () => {
   describe('Board Creation', () => {
     it('Creates a new football squares board', async () => {
       const tx = await program.methods
-        .createBoard(new anchor.BN(gameId))
+        .createBoard(
+          new anchor.BN(gameId),
+          new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          { public: {} },
+          boardConfig,
+        )
         .accounts({
           board: boardPda,
           authority: authority.publicKey,
@@ -75,10 +107,7 @@ describe('Football Squares Program', () => {
       expect(boardAccount.authority.toString()).to.equal(
         authority.publicKey.toString(),
       );
-      expect(boardAccount.finalized).to.be.false;
-      expect(boardAccount.randomized).to.be.false;
-      expect(boardAccount.gameStarted).to.be.false;
-      expect(boardAccount.gameEnded).to.be.false;
+      expect(boardAccount.status).to.deep.equal({ open: {} });
       expect(boardAccount.totalPot.toNumber()).to.equal(0);
 
       // Verify all squares are empty
@@ -98,7 +127,12 @@ describe('Football Squares Program', () => {
     it('Fails to create board with duplicate game ID', async () => {
       try {
         await program.methods
-          .createBoard(new anchor.BN(gameId))
+          .createBoard(
+            new anchor.BN(gameId),
+            new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+            { public: {} },
+            boardConfig,
+          )
           .accounts({
             board: boardPda,
             authority: authority.publicKey,
@@ -150,13 +184,38 @@ describe('Football Squares Program', () => {
       );
       const finalBoardBalance = await provider.connection.getBalance(boardPda);
 
+      // The first purchase also pays rent for the wallet's square counter,
+      // and every purchase pays for the square's mint and token account
+      const counterRent =
+        await provider.connection.getMinimumBalanceForRentExemption(
+          8 + 32 + 32 + 1 + 1,
+        );
+      const mintRent =
+        await provider.connection.getMinimumBalanceForRentExemption(82);
+      const tokenAccountRent =
+        await provider.connection.getMinimumBalanceForRentExemption(170);
       expect(finalBalance).to.be.approximately(
-        initialBalance - squarePrice,
+        initialBalance -
+          squarePrice -
+          counterRent -
+          mintRent -
+          tokenAccountRent,
         10000,
       ); // Allow for tx fees
       expect(finalBoardBalance).to.equal(initialBoardBalance + squarePrice);
     });
 
+    it('Mints the purchased square as a Token-2022 NFT', async () => {
+      const balance = await provider.connection.getTokenAccountBalance(
+        squareTokenAccount(player1.publicKey, 0),
+      );
+      expect(balance.value.amount).to.equal('1');
+      expect(balance.value.decimals).to.equal(0);
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.tokenizedSquares.testn(0)).to.be.true;
+    });
+
     it('Prevents purchasing the same square twice', async () => {
       const squareIndex = 0;
 
@@ -197,14 +256,272 @@ describe('Football Squares Program', () => {
       );
       expect(boardAccount.totalPot.toNumber()).to.equal(squarePrice * 2); // Two squares purchased
     });
+
+    it('Buys several squares in one transaction', async () => {
+      const squareIndices = [20, 21, 22];
+      const squarePrice = 0.01 * LAMPORTS_PER_SOL;
+
+      await program.methods
+        .purchaseSquares(Buffer.from(squareIndices))
+        .accounts({
+          board: boardPda,
+          buyer: player2.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(
+          squareTokenAccounts(
+            program.programId,
+            boardPda,
+            player2.publicKey,
+            squareIndices,
+          ),
+        )
+        .signers([player2])
+        .rpc();
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      for (const squareIndex of squareIndices) {
+        expect(boardAccount.squares[squareIndex].toString()).to.equal(
+          player2.publicKey.toString(),
+        );
+      }
+      expect(boardAccount.totalPot.toNumber()).to.equal(squarePrice * 5);
+    });
+
+    it('Buys nothing when any listed square is taken', async () => {
+      try {
+        await program.methods
+          .purchaseSquares(Buffer.from([30, 31, 0]))
+          .accounts({
+            board: boardPda,
+            buyer: player2.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(
+            squareTokenAccounts(program.programId, boardPda, player2.publicKey, [
+              30, 31, 0,
+            ]),
+          )
+          .signers([player2])
+          .rpc();
+
+        expect.fail('Should have thrown an error for an owned square');
+      } catch (error) {
+        expect(error.message).to.include('SquareAlreadyOwned');
+      }
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.squares[30].toString()).to.equal(
+        PublicKey.default.toString(),
+      );
+    });
+
+    it('Assigns random unsold squares on a quick pick', async () => {
+      const [ticketPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from('quick_pick'),
+          boardPda.toBuffer(),
+          player1.publicKey.toBuffer(),
+        ],
+        program.programId,
+      );
+
+      await program.methods
+        .quickPick(2)
+        .accounts({
+          board: boardPda,
+          buyer: player1.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player1])
+        .rpc();
+
+      const ticket = await program.account.quickPickTicket.fetch(ticketPda);
+      expect(ticket.count).to.equal(2);
+
+      // Nobody can draw early, and the buyer cannot walk away from a pending draw
+      try {
+        await program.methods
+          .releaseQuickPick()
+          .accounts({
+            board: boardPda,
+            buyer: player1.publicKey,
+            releaser: player1.publicKey,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail('Should have thrown an error for a pending quick pick');
+      } catch (error) {
+        expect(error.message).to.include('QuickPickPending');
+      }
+
+      // The draw comes from the reservation slot's hash, which nobody knew
+      // when the reservation was sent
+      const slotHash = await slotHashAt(
+        provider.connection,
+        ticket.reservedSlot.toNumber(),
+      );
+      const before = await program.account.board.fetch(boardPda);
+      const picks = quickPicks(
+        before,
+        boardPda,
+        100,
+        player1.publicKey,
+        2,
+        slotHash,
+      );
+
+      await program.methods
+        .fillQuickPick()
+        .accounts({
+          board: boardPda,
+          buyer: player1.publicKey,
+        })
+        .remainingAccounts(
+          squareTokenAccounts(
+            program.programId,
+            boardPda,
+            player1.publicKey,
+            picks,
+          ),
+        )
+        .signers([player1])
+        .rpc();
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      const picked = boardAccount.squares
+        .map((owner, index) => ({ owner, index }))
+        .filter(
+          ({ owner, index }) =>
+            owner.equals(player1.publicKey) &&
+            before.squares[index].equals(PublicKey.default),
+        );
+      expect(picked.map(({ index }) => index).sort()).to.deep.equal(
+        [...picks].sort(),
+      );
+      expect(
+        boardAccount.totalPot.sub(before.totalPot).toNumber(),
+      ).to.equal(2 * boardAccount.pricePerSquare.toNumber());
+      expect(await provider.connection.getAccountInfo(ticketPda)).to.be.null;
+    });
+
+    it('Rejects a quick pick larger than the unsold squares', async () => {
+      try {
+        await program.methods
+          .quickPick(100)
+          .accounts({
+            board: boardPda,
+            buyer: player1.publicKey,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail('Should have thrown an error for too many squares');
+      } catch (error) {
+        expect(error.message).to.include('NotEnoughSquares');
+      }
+    });
+  });
+
+  describe('Square Pricing', () => {
+    it('Locks the price once a square has sold', async () => {
+      try {
+        await program.methods
+          .updatePrice(new anchor.BN(0.05 * LAMPORTS_PER_SOL))
+          .accounts({ board: boardPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for a locked price');
+      } catch (error) {
+        expect(error.message).to.include('PriceLocked');
+      }
+    });
   });
 
   describe('VRF Randomization', () => {
+    const mockVrf = anchor.workspace.MockVrf as Program<MockVrf>;
+    // The mock oracle must be a registry key; the host cannot post its own randomness
+    const vrfOracle = Keypair.generate();
     let vrfAccount: PublicKey;
+    let randomness: number[];
+
+    before(async () => {
+      // The mock VRF account is bound to the board PDA
+      [vrfAccount] = PublicKey.findProgramAddressSync(
+        [Buffer.from('vrf'), boardPda.toBuffer()],
+        mockVrf.programId,
+      );
+
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          vrfOracle.publicKey,
+          LAMPORTS_PER_SOL,
+        ),
+      );
+
+      await mockVrf.methods
+        .initialize()
+        .accounts({
+          vrf: vrfAccount,
+          consumer: boardPda,
+          oracle: vrfOracle.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([vrfOracle])
+        .rpc();
+
+      await mockVrf.methods
+        .requestRandomness()
+        .accounts({ vrf: vrfAccount, requester: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Keeps headers hidden while sales are open', async () => {
+      try {
+        await program.methods
+          .requestRandomization()
+          .accounts({
+            board: boardPda,
+            vrfAccount: vrfAccount,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for open sales');
+      } catch (error) {
+        expect(error.message).to.include('SalesStillOpen');
+      }
 
-    before(() => {
-      // Mock VRF account for testing
-      vrfAccount = Keypair.generate().publicKey;
+      await program.methods
+        .closeSales()
+        .accounts({ board: boardPda, closer: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    });
+
+    it('Rejects a mock VRF oracle outside the registry', async () => {
+      try {
+        await program.methods
+          .requestRandomization()
+          .accounts({
+            board: boardPda,
+            vrfAccount: vrfAccount,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for an unregistered oracle');
+      } catch (error) {
+        expect(error.message).to.include('Unauthorized');
+      }
+
+      await program.methods.addScoreReporter(vrfOracle.publicKey).rpc();
     });
 
     it('Requests randomization', async () => {
@@ -220,16 +537,47 @@ describe('Football Squares Program', () => {
 
       console.log('Randomization request transaction:', tx);
 
-      // In a real test, we would verify the VRF request was properly formatted
-      // For now, we just ensure the transaction succeeds
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.vrfAccount.toString()).to.equal(
+        vrfAccount.toString(),
+      );
+      expect(boardAccount.vrfRequestedSlot.toNumber()).to.be.greaterThan(0);
+    });
+
+    it('Rejects randomness the VRF account has not produced', async () => {
+      const forged = Array.from({ length: 32 }, () =>
+        Math.floor(Math.random() * 256),
+      );
+
+      try {
+        await program.methods
+          .fulfillVrfCallback(forged)
+          .accounts({
+            board: boardPda,
+            vrfAccount: vrfAccount,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for unfulfilled VRF');
+      } catch (error) {
+        expect(error.message).to.include('InvalidVrfProof');
+      }
     });
 
     it('Fulfills VRF callback with random headers', async () => {
-      // Generate mock randomness (32 bytes)
-      const randomness = Array.from({ length: 32 }, () =>
+      // Generate mock randomness (32 bytes) and post it as the oracle
+      randomness = Array.from({ length: 32 }, () =>
         Math.floor(Math.random() * 256),
       );
 
+      await mockVrf.methods
+        .fulfillRandomness(randomness)
+        .accounts({ vrf: vrfAccount, oracle: vrfOracle.publicKey })
+        .signers([vrfOracle])
+        .rpc();
+
       const tx = await program.methods
         .fulfillVrfCallback(randomness)
         .accounts({
@@ -244,7 +592,7 @@ describe('Football Squares Program', () => {
 
       // Verify board state
       const boardAccount = await program.account.board.fetch(boardPda);
-      expect(boardAccount.randomized).to.be.true;
+      expect(boardAccount.status).to.deep.equal({ randomized: {} });
 
       // Verify headers are set (no longer 10)
       const homeHeadersSet = boardAccount.homeHeaders.every((h) => h !== 10);
@@ -270,10 +618,6 @@ describe('Football Squares Program', () => {
     });
 
     it('Prevents double randomization', async () => {
-      const randomness = Array.from({ length: 32 }, () =>
-        Math.floor(Math.random() * 256),
-      );
-
       try {
         await program.methods
           .fulfillVrfCallback(randomness)
@@ -293,13 +637,30 @@ describe('Football Squares Program', () => {
   });
 
   describe('Score Recording', () => {
+    it('Rejects scores from signers that are not trusted reporters', async () => {
+      try {
+        await program.methods
+          .recordScore(3, 0, 1, false)
+          .accounts({
+            board: boardPda,
+            authority: player1.publicKey,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail('Should have thrown an error for an unregistered reporter');
+      } catch (error) {
+        expect(error.message).to.include('Unauthorized');
+      }
+    });
+
     it('Records game scores', async () => {
-      const homeScore = 14;
-      const awayScore = 7;
-      const quarter = 2;
+      const homeScore = 7;
+      const awayScore = 0;
+      const quarter = 1;
 
       const tx = await program.methods
-        .recordScore(homeScore, awayScore, quarter)
+        .recordScore(homeScore, awayScore, quarter, false)
         .accounts({
           board: boardPda,
           authority: authority.publicKey,
@@ -313,16 +674,101 @@ describe('Football Squares Program', () => {
       expect(boardAccount.homeScore).to.equal(homeScore);
       expect(boardAccount.awayScore).to.equal(awayScore);
       expect(boardAccount.quarter).to.equal(quarter);
-      expect(boardAccount.gameStarted).to.be.true;
+      expect(boardAccount.status).to.deep.equal({ live: {} });
+      expect(boardAccount.periods[quarter - 1].scored).to.be.true;
     });
 
-    it('Records final score and ends game', async () => {
-      const homeScore = 28;
-      const awayScore = 21;
-      const quarter = 4;
+    it('Records the remaining regulation quarters', async () => {
+      for (const [homeScore, awayScore, quarter] of [
+        [14, 7, 2],
+        [21, 14, 3],
+      ]) {
+        await program.methods
+          .recordScore(homeScore, awayScore, quarter, false)
+          .accounts({
+            board: boardPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+      }
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.periods[1].homeScore).to.equal(14);
+      expect(boardAccount.periods[2].awayScore).to.equal(14);
+    });
+
+    it('Rejects quarters reported out of order', async () => {
+      try {
+        await program.methods
+          .recordScore(28, 21, 2, false)
+          .accounts({
+            board: boardPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for a repeated quarter');
+      } catch (error) {
+        expect(error.message).to.include('QuarterOutOfOrder');
+      }
+    });
+
+    it('Rejects scores that go down', async () => {
+      try {
+        await program.methods
+          .recordScore(20, 21, 4, true)
+          .accounts({
+            board: boardPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for a lower score');
+      } catch (error) {
+        expect(error.message).to.include('ScoreDecreased');
+      }
+    });
+
+    it('Corrects an unsettled period with a reason', async () => {
+      await program.methods
+        .correctScore(3, 21, 17, { statCorrection: {} })
+        .accounts({
+          board: boardPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.periods[2].awayScore).to.equal(17);
+      expect(boardAccount.awayScore).to.equal(17);
+    });
+
+    it('Keeps the game open when regulation ends tied', async () => {
+      await program.methods
+        .recordScore(28, 28, 4, false)
+        .accounts({
+          board: boardPda,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+      const boardAccount = await program.account.board.fetch(boardPda);
+      expect(boardAccount.periods[FINAL_QUARTER - 1].scored).to.be.true;
+      expect(boardAccount.status).to.deep.equal({ live: {} });
+    });
+
+    it('Records final score in overtime and ends game', async () => {
+      const homeScore = 34;
+      const awayScore = 28;
+      const quarter = 5;
 
       await program.methods
-        .recordScore(homeScore, awayScore, quarter)
+        .recordScore(homeScore, awayScore, quarter, true)
         .accounts({
           board: boardPda,
           authority: authority.publicKey,
@@ -334,14 +780,45 @@ describe('Football Squares Program', () => {
       expect(boardAccount.homeScore).to.equal(homeScore);
       expect(boardAccount.awayScore).to.equal(awayScore);
       expect(boardAccount.quarter).to.equal(quarter);
-      expect(boardAccount.gameEnded).to.be.true;
+      expect(boardAccount.status).to.deep.equal({ final: {} });
+      // The board pays the final period on the regulation score
+      expect(boardAccount.periods[FINAL_QUARTER - 1].homeScore).to.equal(28);
     });
   });
 
   describe('Winner Settlement', () => {
+    it('Requires earlier periods to settle first', async () => {
+      try {
+        await program.methods
+          .settleWinner(FINAL_QUARTER)
+          .accounts({
+            board: boardPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for unsettled periods');
+      } catch (error) {
+        expect(error.message).to.include('PreviousPeriodUnsettled');
+      }
+
+      // Only two squares sold, so unsold winners fall to the nearest sold square
+      for (let quarter = 1; quarter < FINAL_QUARTER; quarter++) {
+        await program.methods
+          .settleWinner(quarter)
+          .accounts({
+            board: boardPda,
+            authority: authority.publicKey,
+          })
+          .signers([authority])
+          .rpc();
+      }
+    });
+
     it('Settles the winner based on final score', async () => {
       const tx = await program.methods
-        .settleWinner()
+        .settleWinner(FINAL_QUARTER)
         .accounts({
           board: boardPda,
           authority: authority.publicKey,
@@ -352,21 +829,22 @@ describe('Football Squares Program', () => {
       console.log('Winner settlement transaction:', tx);
 
       const boardAccount = await program.account.board.fetch(boardPda);
-      expect(boardAccount.winner.toString()).to.not.equal(
+      const finalPeriod = boardAccount.periods[FINAL_QUARTER - 1];
+      expect(finalPeriod.winner.toString()).to.not.equal(
         PublicKey.default.toString(),
       );
-      expect(boardAccount.payoutAmount.toNumber()).to.be.greaterThan(0);
+      expect(finalPeriod.payoutAmount.toNumber()).to.be.greaterThan(0);
 
       // Winner should be either player1 or player2 (only ones who bought squares)
-      const winnerIsPlayer1 = boardAccount.winner.equals(player1.publicKey);
-      const winnerIsPlayer2 = boardAccount.winner.equals(player2.publicKey);
+      const winnerIsPlayer1 = finalPeriod.winner.equals(player1.publicKey);
+      const winnerIsPlayer2 = finalPeriod.winner.equals(player2.publicKey);
       expect(winnerIsPlayer1 || winnerIsPlayer2).to.be.true;
     });
 
     it('Prevents settling winner twice', async () => {
       try {
         await program.methods
-          .settleWinner()
+          .settleWinner(FINAL_QUARTER)
           .accounts({
             board: boardPda,
             authority: authority.publicKey,
@@ -386,11 +864,12 @@ describe('Football Squares Program', () => {
 
     before(async () => {
       const boardAccount = await program.account.board.fetch(boardPda);
+      const finalPeriod = boardAccount.periods[FINAL_QUARTER - 1];
 
       // Determine which player won
-      if (boardAccount.winner.equals(player1.publicKey)) {
+      if (finalPeriod.winner.equals(player1.publicKey)) {
         winner = player1;
-      } else if (boardAccount.winner.equals(player2.publicKey)) {
+      } else if (finalPeriod.winner.equals(player2.publicKey)) {
         winner = player2;
       } else {
         throw new Error('Unexpected winner');
@@ -399,7 +878,15 @@ describe('Football Squares Program', () => {
 
     it('Pays out the winner', async () => {
       const boardAccount = await program.account.board.fetch(boardPda);
-      const payoutAmount = boardAccount.payoutAmount.toNumber();
+      const finalPeriod = boardAccount.periods[FINAL_QUARTER - 1];
+      const payoutAmount = finalPeriod.payoutAmount.toNumber();
+
+      // Tokenized squares pay whoever holds the square's token
+      const winnerTokenAccount = boardAccount.tokenizedSquares.testn(
+        finalPeriod.squareIndex,
+      )
+        ? squareTokenAccount(winner.publicKey, finalPeriod.squareIndex)
+        : null;
 
       const initialWinnerBalance = await provider.connection.getBalance(
         winner.publicKey,
@@ -408,10 +895,11 @@ describe('Football Squares Program', () => {
         await provider.connection.getBalance(boardPda);
 
       const tx = await program.methods
-        .payoutWinner()
+        .payoutWinner(FINAL_QUARTER)
         .accounts({
           board: boardPda,
           winner: winner.publicKey,
+          winnerTokenAccount,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([winner])
@@ -431,9 +919,9 @@ describe('Football Squares Program', () => {
       );
       expect(finalBoardBalance).to.equal(initialBoardBalance - payoutAmount);
 
-      // Verify payout amount is reset
+      // Verify the period is marked paid
       const updatedBoardAccount = await program.account.board.fetch(boardPda);
-      expect(updatedBoardAccount.payoutAmount.toNumber()).to.equal(0);
+      expect(updatedBoardAccount.periods[FINAL_QUARTER - 1].paid).to.be.true;
     });
 
     it('Prevents unauthorized payout claims', async () => {
@@ -442,7 +930,7 @@ describe('Football Squares Program', () => {
 
       try {
         await program.methods
-          .payoutWinner()
+          .payoutWinner(FINAL_QUARTER)
           .accounts({
             board: boardPda,
             winner: wrongWinner.publicKey,
@@ -460,7 +948,7 @@ describe('Football Squares Program', () => {
     it('Prevents double payout', async () => {
       try {
         await program.methods
-          .payoutWinner()
+          .payoutWinner(FINAL_QUARTER)
           .accounts({
             board: boardPda,
             winner: winner.publicKey,
@@ -498,7 +986,7 @@ describe('Football Squares Program', () => {
     it('Prevents score recording with invalid quarter', async () => {
       try {
         await program.methods
-          .recordScore(10, 7, 5) // Invalid quarter (should be 1-4)
+          .recordScore(10, 7, 15, false) // Past the last overtime period
           .accounts({
             board: boardPda,
             authority: authority.publicKey,
@@ -0,0 +1,225 @@
+// tests/cancel-board.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, squareTokenAccount } from './helpers';
+
+describe('Board Cancellation', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const squarePrice = 0.02 * LAMPORTS_PER_SOL;
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+  let player: Keypair;
+  let stranger: Keypair;
+
+  before(async () => {
+    authority = Keypair.generate();
+    player = Keypair.generate();
+    stranger = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 20000;
+
+    for (const kp of [authority, player, stranger]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(squarePrice),
+        { public: {} },
+        defaultBoardConfig({
+          payoutSplitBps: [2500, 2500, 2500, 2500],
+          unsoldWinnerPolicy: { rollForward: {} },
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .purchaseSquare(7)
+      .accounts({
+        board: boardPda,
+        buyer: player.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+  });
+
+  it('Only lets the host cancel before the settle deadline', async () => {
+    try {
+      await program.methods
+        .cancelBoard()
+        .accounts({ board: boardPda, canceller: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+
+      expect.fail('Should have thrown an error for an early cancellation');
+    } catch (error) {
+      expect(error.message).to.include('CannotCancel');
+    }
+
+    await program.methods
+      .cancelBoard()
+      .accounts({ board: boardPda, canceller: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it('Refunds the exact purchase price once per square', async () => {
+    const initialBoardBalance = await provider.connection.getBalance(boardPda);
+
+    await program.methods
+      .claimRefund(7)
+      .accounts({
+        board: boardPda,
+        owner: player.publicKey,
+        ownerTokenAccount: squareTokenAccount(
+          program.programId,
+          boardPda,
+          player.publicKey,
+          7,
+        ),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    const finalBoardBalance = await provider.connection.getBalance(boardPda);
+    expect(finalBoardBalance).to.equal(initialBoardBalance - squarePrice);
+
+    try {
+      await program.methods
+        .claimRefund(7)
+        .accounts({
+          board: boardPda,
+          owner: player.publicKey,
+          ownerTokenAccount: squareTokenAccount(
+            program.programId,
+            boardPda,
+            player.publicKey,
+            7,
+          ),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a repeated refund');
+    } catch (error) {
+      expect(error.message).to.include('AlreadyRefunded');
+    }
+  });
+
+  it('Returns the square counter rent once the board is cancelled', async () => {
+    const [walletSquaresPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('wallet_squares'),
+        boardPda.toBuffer(),
+        player.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .closeWalletSquares()
+      .accounts({ board: boardPda, wallet: player.publicKey })
+      .signers([player])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(walletSquaresPda)).to.be
+      .null;
+  });
+
+  it('Stops the host from cancelling after kickoff', async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const lateGameId = gameId + 1;
+    const [lateBoardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(lateGameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(lateGameId),
+        new anchor.BN(squarePrice),
+        { public: {} },
+        defaultBoardConfig({
+          payoutSplitBps: [2500, 2500, 2500, 2500],
+          unsoldWinnerPolicy: { rollForward: {} },
+          salesOpenAt: new anchor.BN(now - 120),
+          salesCloseAt: new anchor.BN(now - 60),
+          kickoffAt: new anchor.BN(now - 60),
+        }),
+      )
+      .accounts({
+        board: lateBoardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    try {
+      await program.methods
+        .cancelBoard()
+        .accounts({ board: lateBoardPda, canceller: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a cancellation after kickoff');
+    } catch (error) {
+      expect(error.message).to.include('CannotCancel');
+    }
+  });
+
+  it('Stops sales on a cancelled board', async () => {
+    try {
+      await program.methods
+        .purchaseSquare(8)
+        .accounts({
+          board: boardPda,
+          buyer: stranger.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([stranger])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a cancelled board');
+    } catch (error) {
+      expect(error.message).to.include('BoardCancelled');
+    }
+  });
+});
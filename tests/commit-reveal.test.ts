@@ -0,0 +1,289 @@
+// tests/commit-reveal.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, squareTokenAccount } from './helpers';
+
+describe('Commit-Reveal Randomization', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const seed = randomBytes(32);
+  const seedCommitment = Array.from(createHash('sha256').update(seed).digest());
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+  let player: Keypair;
+
+  before(async () => {
+    authority = Keypair.generate();
+    player = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 10000;
+
+    for (const kp of [authority, player]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    const revealDeadline = Math.floor(Date.now() / 1000) + 3600;
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { inviteOnly: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment,
+              revealDeadline: new anchor.BN(revealDeadline),
+            },
+          },
+          unsoldWinnerPolicy: { refundProRata: {} },
+          settleDeadline: new anchor.BN(revealDeadline + 86400),
+          salesOpenAt: new anchor.BN(revealDeadline - 3660),
+          salesCloseAt: new anchor.BN(revealDeadline - 1800),
+          kickoffAt: new anchor.BN(revealDeadline),
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Sells squares before the seed is revealed', async () => {
+    await program.methods
+      .purchaseSquare(42)
+      .accounts({
+        board: boardPda,
+        buyer: player.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ open: {} });
+    expect(boardAccount.squares[42].toString()).to.equal(
+      player.publicKey.toString(),
+    );
+  });
+
+  it('Rejects a reveal while sales are open', async () => {
+    try {
+      await program.methods
+        .revealSeed(Array.from(seed))
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error for open sales');
+    } catch (error) {
+      expect(error.message).to.include('SalesStillOpen');
+    }
+  });
+
+  it('Rejects a seed that does not match the commitment', async () => {
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    // The closing slot's hash is mixed into the revealed seed
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.salesClosedSlot.toNumber()).to.be.greaterThan(0);
+
+    try {
+      await program.methods
+        .revealSeed(Array.from(randomBytes(32)))
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a wrong seed');
+    } catch (error) {
+      expect(error.message).to.include('InvalidSeedReveal');
+    }
+  });
+
+  it("Lets anyone pin the closing slot's hash before it ages out", async () => {
+    await program.methods
+      .recordClosingSlotHash()
+      .accounts({ board: boardPda })
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.salesClosedSlotHash.some((byte) => byte !== 0)).to.be
+      .true;
+
+    try {
+      await program.methods
+        .recordClosingSlotHash()
+        .accounts({ board: boardPda })
+        .rpc();
+
+      expect.fail('Should have thrown an error for a recorded hash');
+    } catch (error) {
+      expect(error.message).to.include('ClosingSlotHashRecorded');
+    }
+  });
+
+  it('Derives headers from the revealed seed', async () => {
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ randomized: {} });
+    expect(new Set(boardAccount.homeHeaders).size).to.equal(10);
+    expect(new Set(boardAccount.awayHeaders).size).to.equal(10);
+  });
+
+  it('Does not refund a revealed board', async () => {
+    try {
+      await program.methods
+        .claimRefund(42)
+        .accounts({
+          board: boardPda,
+          owner: player.publicKey,
+          ownerTokenAccount: squareTokenAccount(
+            program.programId,
+            boardPda,
+            player.publicKey,
+            42,
+          ),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a revealed board');
+    } catch (error) {
+      expect(error.message).to.include('NotRefundable');
+    }
+  });
+
+  describe('when the host misses the reveal deadline', () => {
+    let lateBoardPda: PublicKey;
+    let revealDeadline: number;
+
+    before(async () => {
+      const lateGameId = gameId + 1;
+      [lateBoardPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from('board'),
+          new anchor.BN(lateGameId).toArrayLike(Buffer, 'le', 8),
+        ],
+        program.programId,
+      );
+
+      const now = Math.floor(Date.now() / 1000);
+      revealDeadline = now + 6;
+      await program.methods
+        .createBoard(
+          new anchor.BN(lateGameId),
+          new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          { inviteOnly: {} },
+          defaultBoardConfig({
+            randomnessMode: {
+              commitReveal: {
+                seedCommitment,
+                revealDeadline: new anchor.BN(revealDeadline),
+              },
+            },
+            salesCloseAt: new anchor.BN(now + 3),
+            kickoffAt: new anchor.BN(revealDeadline),
+          }),
+        )
+        .accounts({
+          board: lateBoardPda,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+
+      await program.methods
+        .purchaseSquare(7)
+        .accounts({
+          board: lateBoardPda,
+          buyer: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      await program.methods
+        .closeSales()
+        .accounts({ board: lateBoardPda, closer: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      // Wait out the reveal deadline
+      while (Math.floor(Date.now() / 1000) <= revealDeadline + 1) {
+        await new Promise((resolve) => setTimeout(resolve, 1000));
+      }
+    });
+
+    it('Rejects a reveal after the deadline', async () => {
+      try {
+        await program.methods
+          .revealSeed(Array.from(seed))
+          .accounts({ board: lateBoardPda, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+
+        expect.fail('Should have thrown an error for a late reveal');
+      } catch (error) {
+        expect(error.message).to.include('RevealDeadlinePassed');
+      }
+    });
+
+    it('Refunds buyers of an unrevealed board', async () => {
+      const before = await provider.connection.getBalance(player.publicKey);
+
+      await program.methods
+        .claimRefund(7)
+        .accounts({
+          board: lateBoardPda,
+          owner: player.publicKey,
+          ownerTokenAccount: squareTokenAccount(
+            program.programId,
+            lateBoardPda,
+            player.publicKey,
+            7,
+          ),
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      const after = await provider.connection.getBalance(player.publicKey);
+      expect(after - before).to.be.greaterThan(0.009 * LAMPORTS_PER_SOL);
+    });
+  });
+});
@@ -0,0 +1,95 @@
+// tests/grid-size.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, squareTokenAccounts } from './helpers';
+
+describe('5x5 Grid', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+  let player: Keypair;
+
+  const purchase = (squareIndex: number) =>
+    program.methods
+      .purchaseSquares(Buffer.from([squareIndex]))
+      .accounts({
+        board: boardPda,
+        buyer: player.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        squareTokenAccounts(program.programId, boardPda, player.publicKey, [
+          squareIndex,
+        ]),
+      )
+      .signers([player])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    player = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 100000;
+
+    for (const kp of [authority, player]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          gridSize: { fiveByFive: {} },
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Sells the last of the 25 squares', async () => {
+    await purchase(24);
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.gridSize).to.deep.equal({ fiveByFive: {} });
+    expect(boardAccount.squares[24].toString()).to.equal(
+      player.publicKey.toString(),
+    );
+  });
+
+  it('Rejects squares beyond the 5x5 grid', async () => {
+    try {
+      await purchase(25);
+
+      expect.fail('Should have thrown an error for a square off the grid');
+    } catch (error) {
+      expect(error.message).to.include('InvalidSquareIndex');
+    }
+  });
+});
@@ -0,0 +1,217 @@
+// tests/helpers.ts
+import { BN, IdlTypes, Program } from '@coral-xyz/anchor';
+import {
+  AccountMeta,
+  Connection,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  TransactionInstruction,
+} from '@solana/web3.js';
+import { createHash } from 'crypto';
+import { Squares } from '../target/types/squares';
+
+export type BoardConfig = IdlTypes<Squares>['boardConfig'];
+
+// A plain 10x10 VRF board whose sales are open now and close at kickoff in an
+// hour; tests override only the fields they exercise
+export const defaultBoardConfig = (
+  overrides: Partial<BoardConfig> = {},
+): BoardConfig => {
+  const now = Math.floor(Date.now() / 1000);
+
+  return {
+    randomnessMode: { vrf: {} },
+    headerMode: { salesFirst: {} },
+    payoutSplitBps: [0, 0, 0, 10000],
+    unsoldWinnerPolicy: { nearestSold: {} },
+    settleDeadline: new BN(now + 86400),
+    salesOpenAt: new BN(now - 60),
+    salesCloseAt: new BN(now + 3600),
+    kickoffAt: new BN(now + 3600),
+    maxSquaresPerWallet: 0,
+    holdDurationSecs: new BN(0),
+    holdDeposit: new BN(0),
+    expiredHoldPolicy: { refund: {} },
+    resaleRoyaltyBps: 0,
+    hostCanReport: true,
+    scoreQuorum: 1,
+    disputeWindowSecs: new BN(0),
+    disputeBond: new BN(0),
+    arbiter: PublicKey.default,
+    gridSize: { tenByTen: {} },
+    sportRules: {
+      periods: 4,
+      overtime: { regulationScore: {} },
+      scoringDigit: { last: {} },
+      maxPointsPerPeriod: 50,
+    },
+    ...overrides,
+  };
+};
+
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  'BPFLoaderUpgradeab1e11111111111111111111111',
+);
+
+// Only the program's upgrade authority (the provider wallet under `anchor test`)
+// may create the oracle registry
+export const initializeOracleRegistry = (program: Program<Squares>) => {
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID,
+  );
+
+  return program.methods.initializeOracleRegistry().accounts({ programData });
+};
+
+export const TOKEN_2022_PROGRAM_ID = new PublicKey(
+  'TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb',
+);
+export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
+  'ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL',
+);
+
+export const squareMint = (
+  programId: PublicKey,
+  board: PublicKey,
+  squareIndex: number,
+) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from('square_mint'), board.toBuffer(), Buffer.from([squareIndex])],
+    programId,
+  )[0];
+
+export const squareTokenAccount = (
+  programId: PublicKey,
+  board: PublicKey,
+  owner: PublicKey,
+  squareIndex: number,
+) =>
+  PublicKey.findProgramAddressSync(
+    [
+      owner.toBuffer(),
+      TOKEN_2022_PROGRAM_ID.toBuffer(),
+      squareMint(programId, board, squareIndex).toBuffer(),
+    ],
+    ASSOCIATED_TOKEN_PROGRAM_ID,
+  )[0];
+
+// Remaining accounts for purchase_squares and fill_quick_pick: a (mint, token
+// account) pair per square, in purchase order
+export const squareTokenAccounts = (
+  programId: PublicKey,
+  board: PublicKey,
+  buyer: PublicKey,
+  squareIndices: number[],
+): AccountMeta[] =>
+  squareIndices.flatMap((squareIndex) => [
+    {
+      pubkey: squareMint(programId, board, squareIndex),
+      isWritable: true,
+      isSigner: false,
+    },
+    {
+      pubkey: squareTokenAccount(programId, board, buyer, squareIndex),
+      isWritable: true,
+      isSigner: false,
+    },
+  ]);
+
+const sha256 = (...parts: Buffer[]) =>
+  createHash('sha256').update(Buffer.concat(parts)).digest();
+
+// Hash of `slot` from the SlotHashes sysvar, waiting until the slot is hashed
+export const slotHashAt = async (
+  connection: Connection,
+  slot: number,
+): Promise<Buffer> => {
+  for (;;) {
+    const { data } = (await connection.getAccountInfo(SYSVAR_SLOT_HASHES_PUBKEY))!;
+    const entries = Number(data.readBigUInt64LE(0));
+    for (let i = 0; i < entries; i++) {
+      const offset = 8 + 40 * i;
+      if (Number(data.readBigUInt64LE(offset)) === slot) {
+        return data.subarray(offset + 8, offset + 40);
+      }
+    }
+    await new Promise((resolve) => setTimeout(resolve, 400));
+  }
+};
+
+// Mirrors quick_pick_seed and pick_squares in the program, so the caller
+// knows which square mints to pass when filling a reserved quick pick
+export const quickPicks = (
+  board: { squares: PublicKey[]; heldSquares: BN },
+  boardPda: PublicKey,
+  squareCount: number,
+  buyer: PublicKey,
+  count: number,
+  slotHash: Buffer,
+): number[] => {
+  const seed = sha256(slotHash, boardPda.toBuffer(), buyer.toBuffer());
+  const domain = Buffer.from('squares:quick_pick');
+  const blockOf = (index: number) => {
+    const counter = Buffer.alloc(4);
+    counter.writeUInt32LE(index);
+    return sha256(domain, seed, counter);
+  };
+  let blockIndex = 0;
+  let block = blockOf(0);
+  let cursor = 0;
+  const nextBelow = (bound: number) => {
+    const limit = 256 - (256 % bound);
+    for (;;) {
+      if (cursor === block.length) {
+        block = blockOf(++blockIndex);
+        cursor = 0;
+      }
+      const byte = block[cursor++];
+      if (byte < limit) return byte % bound;
+    }
+  };
+
+  const pool = [...Array(squareCount).keys()].filter(
+    (index) =>
+      board.squares[index].equals(PublicKey.default) &&
+      !board.heldSquares.testn(index),
+  );
+  // Squares sold since the reservation shrink the draw
+  const picks = Math.min(count, pool.length);
+  for (let i = 0; i < picks; i++) {
+    const j = i + nextBelow(pool.length - i);
+    [pool[i], pool[j]] = [pool[j], pool[i]];
+  }
+  return pool.slice(0, picks);
+};
+
+// Creates `owner`'s token account for a square's NFT if it does not exist,
+// for the resale paths that move the token to an existing account
+export const createSquareTokenAccountIx = (
+  programId: PublicKey,
+  board: PublicKey,
+  payer: PublicKey,
+  owner: PublicKey,
+  squareIndex: number,
+) =>
+  new TransactionInstruction({
+    programId: ASSOCIATED_TOKEN_PROGRAM_ID,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      {
+        pubkey: squareTokenAccount(programId, board, owner, squareIndex),
+        isSigner: false,
+        isWritable: true,
+      },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      {
+        pubkey: squareMint(programId, board, squareIndex),
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_2022_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    // CreateIdempotent
+    data: Buffer.from([1]),
+  });
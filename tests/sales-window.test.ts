@@ -0,0 +1,275 @@
+// tests/sales-window.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { MockVrf } from '../target/types/mock_vrf';
+import { defaultBoardConfig, initializeOracleRegistry } from './helpers';
+
+describe('Sales Window', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  let authority: Keypair;
+  let player: Keypair;
+
+  const createBoard = async (
+    salesOpenAt: number,
+    salesCloseAt: number,
+    maxSquaresPerWallet = 0,
+    headerMode: object = { salesFirst: {} },
+  ) => {
+    const gameId = Math.floor(Math.random() * 10000) + 60000;
+    const [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          settleDeadline: new anchor.BN(salesCloseAt + 86400),
+          salesOpenAt: new anchor.BN(salesOpenAt),
+          salesCloseAt: new anchor.BN(salesCloseAt),
+          kickoffAt: new anchor.BN(salesCloseAt + 600),
+          maxSquaresPerWallet,
+          headerMode,
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    return boardPda;
+  };
+
+  const purchase = (boardPda: PublicKey, squareIndex: number) =>
+    program.methods
+      .purchaseSquare(squareIndex)
+      .accounts({
+        board: boardPda,
+        buyer: player.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    player = Keypair.generate();
+
+    for (const kp of [authority, player]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+  });
+
+  it('Rejects purchases before sales open', async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const boardPda = await createBoard(now + 3600, now + 7200);
+
+    try {
+      await purchase(boardPda, 10);
+
+      expect.fail('Should have thrown an error before sales open');
+    } catch (error) {
+      expect(error.message).to.include('SalesNotOpen');
+    }
+  });
+
+  it('Stops purchases at the cutoff and lets anyone close sales', async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const boardPda = await createBoard(now - 60, now + 3);
+
+    await purchase(boardPda, 10);
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    try {
+      await purchase(boardPda, 11);
+
+      expect.fail('Should have thrown an error after the cutoff');
+    } catch (error) {
+      expect(error.message).to.include('SalesClosed');
+    }
+
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: player.publicKey })
+      .signers([player])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ salesClosed: {} });
+  });
+
+  it('Caps the squares one wallet can buy across purchase paths', async () => {
+    const now = Math.floor(Date.now() / 1000);
+    const boardPda = await createBoard(now - 60, now + 3600, 2);
+
+    try {
+      await program.methods
+        .purchaseSquares(Buffer.from([1, 2, 3]))
+        .accounts({
+          board: boardPda,
+          buyer: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      expect.fail('Should have thrown an error over the wallet limit');
+    } catch (error) {
+      expect(error.message).to.include('WalletLimitReached');
+    }
+
+    await purchase(boardPda, 1);
+    await purchase(boardPda, 2);
+
+    try {
+      await program.methods
+        .quickPick(1)
+        .accounts({
+          board: boardPda,
+          buyer: player.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([player])
+        .rpc();
+
+      expect.fail('Should have thrown an error over the wallet limit');
+    } catch (error) {
+      expect(error.message).to.include('WalletLimitReached');
+    }
+
+    const [walletSquaresPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('wallet_squares'),
+        boardPda.toBuffer(),
+        player.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+    const walletSquares =
+      await program.account.walletSquares.fetch(walletSquaresPda);
+    expect(walletSquares.squaresOwned).to.equal(2);
+  });
+
+  it('Lets the host close a headers-first board once its headers are drawn', async () => {
+    const mockVrf = anchor.workspace.MockVrf as Program<MockVrf>;
+    const vrfOracle = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const boardPda = await createBoard(now - 60, now + 3600, 0, {
+      headersFirst: {},
+    });
+
+    try {
+      await program.methods
+        .closeSales()
+        .accounts({ board: boardPda, closer: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error before the headers are drawn');
+    } catch (error) {
+      expect(error.message).to.include('NotRandomized');
+    }
+
+    // Headers-first boards draw their headers while sales are still open
+    const [vrfAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from('vrf'), boardPda.toBuffer()],
+      mockVrf.programId,
+    );
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        vrfOracle.publicKey,
+        LAMPORTS_PER_SOL,
+      ),
+    );
+    await program.methods.addScoreReporter(vrfOracle.publicKey).rpc();
+    await mockVrf.methods
+      .initialize()
+      .accounts({
+        vrf: vrfAccount,
+        consumer: boardPda,
+        oracle: vrfOracle.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([vrfOracle])
+      .rpc();
+    await mockVrf.methods
+      .requestRandomness()
+      .accounts({ vrf: vrfAccount, requester: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .requestRandomization()
+      .accounts({
+        board: boardPda,
+        vrfAccount,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+    const randomness = Array.from({ length: 32 }, () =>
+      Math.floor(Math.random() * 256),
+    );
+    await mockVrf.methods
+      .fulfillRandomness(randomness)
+      .accounts({ vrf: vrfAccount, oracle: vrfOracle.publicKey })
+      .signers([vrfOracle])
+      .rpc();
+    await program.methods
+      .fulfillVrfCallback(randomness)
+      .accounts({
+        board: boardPda,
+        vrfAccount,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await purchase(boardPda, 10);
+
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    // The board stays randomized; only the cutoff moves in
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ randomized: {} });
+    expect(boardAccount.salesCloseAt.toNumber()).to.be.lessThan(now + 3600);
+
+    try {
+      await purchase(boardPda, 11);
+
+      expect.fail('Should have thrown an error after closing sales');
+    } catch (error) {
+      expect(error.message).to.include('SalesClosed');
+    }
+  });
+});
@@ -0,0 +1,167 @@
+// tests/score-consensus.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, initializeOracleRegistry } from './helpers';
+
+describe('Multi-Reporter Score Consensus', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const seed = randomBytes(32);
+  const reporters = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+
+  const submit = (reporter: Keypair, home: number, away: number, quarter: number) =>
+    program.methods
+      .submitScore(home, away, quarter, false)
+      .accounts({ board: boardPda, authority: reporter.publicKey })
+      .signers([reporter])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 30000;
+
+    for (const kp of [authority, ...reporters]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    // The provider wallet administers the registry in the test validator
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+    for (const reporter of reporters) {
+      await program.methods.addScoreReporter(reporter.publicKey).rpc();
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          unsoldWinnerPolicy: { refundProRata: {} },
+          salesCloseAt: new anchor.BN(now + 1800),
+          hostCanReport: false,
+          scoreQuorum: 2,
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Rejects single-reporter scores on quorum boards', async () => {
+    try {
+      await program.methods
+        .recordScore(7, 0, 1, false)
+        .accounts({ board: boardPda, authority: reporters[0].publicKey })
+        .signers([reporters[0]])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a quorum board');
+    } catch (error) {
+      expect(error.message).to.include('QuorumRequired');
+    }
+  });
+
+  it('Waits for a quorum before making a score official', async () => {
+    await submit(reporters[0], 7, 0, 1);
+
+    let boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].scored).to.be.false;
+
+    // A conflicting report is recorded but does not count towards the quorum
+    await submit(reporters[1], 0, 7, 1);
+    boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].scored).to.be.false;
+
+    await submit(reporters[2], 7, 0, 1);
+    boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].scored).to.be.true;
+    expect(boardAccount.periods[0].homeScore).to.equal(7);
+
+    const [tallyPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('score_tally'), boardPda.toBuffer(), Buffer.from([1])],
+      program.programId,
+    );
+    const tally = await program.account.scoreTally.fetch(tallyPda);
+    expect(tally.official).to.be.true;
+    expect(tally.disagreements).to.equal(1);
+  });
+
+  it('Rejects a second vote from the same reporter', async () => {
+    try {
+      await submit(reporters[0], 7, 0, 1);
+
+      expect.fail('Should have thrown an error for a duplicate vote');
+    } catch (error) {
+      expect(error.message).to.include('DuplicateScoreVote');
+    }
+  });
+
+  it('Needs a quorum to correct an official score', async () => {
+    const correct = (reporter: Keypair) =>
+      program.methods
+        .correctScore(1, 7, 3, { statCorrection: {} })
+        .accounts({ board: boardPda, authority: reporter.publicKey })
+        .signers([reporter])
+        .rpc();
+
+    await correct(reporters[0]);
+    let boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].awayScore).to.equal(0);
+
+    await correct(reporters[1]);
+    boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].awayScore).to.equal(3);
+  });
+});
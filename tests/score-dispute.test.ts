@@ -0,0 +1,216 @@
+// tests/score-dispute.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import {
+  defaultBoardConfig,
+  initializeOracleRegistry,
+  squareTokenAccount,
+} from './helpers';
+
+describe('Score Dispute Window', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const seed = randomBytes(32);
+  const disputeWindowSecs = 5;
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let disputePda: PublicKey;
+  let treasuryPda: PublicKey;
+  let authority: Keypair;
+  let arbiter: Keypair;
+  let player: Keypair;
+
+  before(async () => {
+    authority = Keypair.generate();
+    arbiter = Keypair.generate();
+    player = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 40000;
+
+    for (const kp of [authority, arbiter, player]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    for (const init of [
+      initializeOracleRegistry(program),
+      program.methods.initializeTreasury(),
+    ]) {
+      try {
+        await init.rpc();
+      } catch (error) {
+        expect(error.message).to.include('already in use');
+      }
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+    [disputePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('dispute'), boardPda.toBuffer(), Buffer.from([4])],
+      program.programId,
+    );
+    [treasuryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from('treasury')],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          unsoldWinnerPolicy: { refundProRata: {} },
+          salesCloseAt: new anchor.BN(now + 1800),
+          disputeWindowSecs: new anchor.BN(disputeWindowSecs),
+          disputeBond: new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+          arbiter: arbiter.publicKey,
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .purchaseSquare(0)
+      .accounts({
+        board: boardPda,
+        buyer: player.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    for (const quarter of [1, 2, 3, 4]) {
+      await program.methods
+        .recordScore(7, 3, quarter, quarter === 4)
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    }
+  });
+
+  it('Holds settlement while the dispute window is open', async () => {
+    try {
+      await program.methods
+        .settleWinner(4)
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error for an open window');
+    } catch (error) {
+      expect(error.message).to.include('DisputeWindowOpen');
+    }
+  });
+
+  it('Lets a square owner dispute the final score', async () => {
+    await program.methods
+      .openDispute(4, 10, 3, 0)
+      .accounts({
+        board: boardPda,
+        dispute: disputePda,
+        disputer: player.publicKey,
+        disputerTokenAccount: squareTokenAccount(
+          program.programId,
+          boardPda,
+          player.publicKey,
+          0,
+        ),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.openDisputes).to.equal(1);
+  });
+
+  it('Leaves a disputed score to the arbiter', async () => {
+    try {
+      await program.methods
+        .correctScore(4, 10, 3, { statCorrection: {} })
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      expect.fail('Should have thrown an error for an open dispute');
+    } catch (error) {
+      expect(error.message).to.include('DisputeOpen');
+    }
+  });
+
+  it('Corrects the score when the arbiter upholds the dispute', async () => {
+    await program.methods
+      .resolveDispute(true)
+      .accounts({
+        board: boardPda,
+        dispute: disputePda,
+        disputer: player.publicKey,
+        treasury: treasuryPda,
+        arbiter: arbiter.publicKey,
+      })
+      .signers([arbiter])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.openDisputes).to.equal(0);
+    expect(boardAccount.periods[3].homeScore).to.equal(10);
+    expect(boardAccount.homeScore).to.equal(10);
+    expect(await provider.connection.getAccountInfo(disputePda)).to.be.null;
+  });
+
+  it('Settles once the window has passed', async () => {
+    await new Promise((resolve) =>
+      setTimeout(resolve, (disputeWindowSecs + 1) * 1000),
+    );
+
+    await program.methods
+      .settleWinner(4)
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[3].settled).to.be.true;
+  });
+});
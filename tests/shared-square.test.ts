@@ -0,0 +1,463 @@
+// tests/shared-square.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import {
+  defaultBoardConfig,
+  initializeOracleRegistry,
+  squareTokenAccount,
+} from './helpers';
+
+describe('Shared Squares', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const squarePrice = 0.01 * LAMPORTS_PER_SOL;
+  const squareIndex = 42;
+  const seed = randomBytes(32);
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let sharesPda: PublicKey;
+  let authority: Keypair;
+  let buyer: Keypair;
+  let friend: Keypair;
+
+  const claim = (coOwner: Keypair) =>
+    program.methods
+      .payoutWinner(4)
+      .accounts({
+        board: boardPda,
+        winner: coOwner.publicKey,
+        winnerTokenAccount: null,
+        winnerShares: sharesPda,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([coOwner])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    buyer = Keypair.generate();
+    friend = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 90000;
+
+    for (const kp of [authority, buyer, friend]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+    [sharesPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('square_shares'),
+        boardPda.toBuffer(),
+        Buffer.from([squareIndex]),
+      ],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(squarePrice),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          // The shared square is the only one sold, so it wins every period
+          unsoldWinnerPolicy: { nearestSold: {} },
+          salesCloseAt: new anchor.BN(now + 1800),
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Rejects a shared purchase with a single co-owner', async () => {
+    try {
+      await program.methods
+        .purchaseSharedSquare(squareIndex, [
+          { owner: buyer.publicKey, weight: 1 },
+        ])
+        .accounts({
+          board: boardPda,
+          buyer: buyer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a lone co-owner');
+    } catch (error) {
+      expect(error.message).to.include('InvalidCoOwners');
+    }
+  });
+
+  it('Buys a square split between co-owners', async () => {
+    await program.methods
+      .purchaseSharedSquare(squareIndex, [
+        { owner: buyer.publicKey, weight: 2 },
+        { owner: friend.publicKey, weight: 1 },
+      ])
+      .accounts({
+        board: boardPda,
+        buyer: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const shares = await program.account.squareShares.fetch(sharesPda);
+    expect(shares.coOwners.map((coOwner) => coOwner.weight)).to.deep.equal([
+      2, 1,
+    ]);
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.sharedSquares.testn(squareIndex)).to.be.true;
+    expect(boardAccount.totalPot.toNumber()).to.equal(squarePrice);
+
+    // The token is a frozen receipt; co-owners are paid from their shares
+    const { value } = await provider.connection.getParsedAccountInfo(
+      squareTokenAccount(
+        program.programId,
+        boardPda,
+        buyer.publicKey,
+        squareIndex,
+      ),
+    );
+    const { info } = (value.data as anchor.web3.ParsedAccountData).parsed;
+    expect(info.tokenAmount.amount).to.equal('1');
+    expect(info.state).to.equal('frozen');
+  });
+
+  it('Pays each co-owner their weighted share of the win', async () => {
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    const scores = [
+      [7, 0],
+      [14, 3],
+      [17, 10],
+      [24, 13],
+    ];
+    for (const [i, [home, away]] of scores.entries()) {
+      await program.methods
+        .recordScore(home, away, i + 1, i === scores.length - 1)
+        .accounts({ board: boardPda, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    }
+    for (let quarter = 1; quarter <= 4; quarter++) {
+      await program.methods
+        .settleWinner(quarter)
+        .accounts({
+          board: boardPda,
+          authority: authority.publicKey,
+          winnerShares: sharesPda,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    const friendBefore = await provider.connection.getBalance(
+      friend.publicKey,
+    );
+    await claim(friend);
+    const friendAfter = await provider.connection.getBalance(friend.publicKey);
+    // Dust from the 2:1 split goes to the first co-owner
+    expect(friendAfter - friendBefore).to.be.approximately(
+      Math.floor(squarePrice / 3),
+      10000,
+    );
+
+    try {
+      await claim(friend);
+
+      expect.fail('Should have thrown an error for a second claim');
+    } catch (error) {
+      expect(error.message).to.include('NoPayout');
+    }
+
+    await claim(buyer);
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[3].paid).to.be.true;
+    expect(boardAccount.status).to.deep.equal({ paid: {} });
+  });
+
+  describe('with a split outcome or a refund', () => {
+    let other: Keypair;
+
+    const sharesOf = (board: PublicKey, index: number) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from('square_shares'), board.toBuffer(), Buffer.from([index])],
+        program.programId,
+      )[0];
+
+    const createSharedBoard = async (
+      id: number,
+      config: Parameters<typeof defaultBoardConfig>[0],
+    ) => {
+      const [board] = PublicKey.findProgramAddressSync(
+        [Buffer.from('board'), new anchor.BN(id).toArrayLike(Buffer, 'le', 8)],
+        program.programId,
+      );
+      await program.methods
+        .createBoard(
+          new anchor.BN(id),
+          new anchor.BN(squarePrice),
+          { public: {} },
+          defaultBoardConfig(config),
+        )
+        .accounts({
+          board,
+          authority: authority.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .purchaseSharedSquare(squareIndex, [
+          { owner: buyer.publicKey, weight: 2 },
+          { owner: friend.publicKey, weight: 1 },
+        ])
+        .accounts({
+          board,
+          buyer: buyer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+      return board;
+    };
+
+    before(async () => {
+      other = Keypair.generate();
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          other.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    });
+
+    it('Splits a pro-rata share among co-owners by weight', async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const board = await createSharedBoard(gameId + 1, {
+        randomnessMode: {
+          commitReveal: {
+            seedCommitment: Array.from(
+              createHash('sha256').update(seed).digest(),
+            ),
+            revealDeadline: new anchor.BN(now + 3600),
+          },
+        },
+        unsoldWinnerPolicy: { refundProRata: {} },
+        salesCloseAt: new anchor.BN(now + 1800),
+      });
+      const shares = sharesOf(board, squareIndex);
+
+      await program.methods
+        .purchaseSquare(squareIndex + 1)
+        .accounts({
+          board,
+          buyer: other.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([other])
+        .rpc();
+      await program.methods
+        .closeSales()
+        .accounts({ board, closer: authority.publicKey })
+        .signers([authority])
+        .rpc();
+      await program.methods
+        .revealSeed(Array.from(seed))
+        .accounts({ board, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      // Every period lands on unsold square 0, so the pot is refunded pro rata
+      const { homeHeaders, awayHeaders } =
+        await program.account.board.fetch(board);
+      for (let quarter = 1; quarter <= 4; quarter++) {
+        await program.methods
+          .recordScore(homeHeaders[0], awayHeaders[0], quarter, quarter === 4)
+          .accounts({ board, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      }
+      for (let quarter = 1; quarter <= 4; quarter++) {
+        await program.methods
+          .settleWinner(quarter)
+          .accounts({ board, authority: authority.publicKey })
+          .signers([authority])
+          .rpc();
+      }
+
+      const claimShare = (claimant: Keypair) =>
+        program.methods
+          .claimPeriodShare(4, squareIndex)
+          .accounts({
+            board,
+            claimant: claimant.publicKey,
+            claimantTokenAccount: null,
+            claimantShares: shares,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([claimant])
+          .rpc();
+
+      // Two squares sold, so the shared square's part is half the pot
+      const squarePart = squarePrice;
+      const friendBefore = await provider.connection.getBalance(
+        friend.publicKey,
+      );
+      await claimShare(friend);
+      const friendAfter = await provider.connection.getBalance(
+        friend.publicKey,
+      );
+      expect(friendAfter - friendBefore).to.be.approximately(
+        Math.floor(squarePart / 3),
+        10000,
+      );
+
+      try {
+        await claimShare(friend);
+
+        expect.fail('Should have thrown an error for a second claim');
+      } catch (error) {
+        expect(error.message).to.include('NoPayout');
+      }
+
+      try {
+        await claimShare(other);
+
+        expect.fail('Should have thrown an error for a non co-owner');
+      } catch (error) {
+        expect(error.message).to.include('NotSquareOwner');
+      }
+
+      // The square's claim completes only once every co-owner has claimed
+      let boardAccount = await program.account.board.fetch(board);
+      expect(boardAccount.periods[3].claimedSquares.testn(squareIndex)).to.be
+        .false;
+
+      const buyerBefore = await provider.connection.getBalance(
+        buyer.publicKey,
+      );
+      await claimShare(buyer);
+      const buyerAfter = await provider.connection.getBalance(buyer.publicKey);
+      expect(buyerAfter - buyerBefore).to.be.approximately(
+        squarePart - Math.floor(squarePart / 3),
+        10000,
+      );
+
+      boardAccount = await program.account.board.fetch(board);
+      expect(boardAccount.periods[3].claimedSquares.testn(squareIndex)).to.be
+        .true;
+    });
+
+    it('Refunds each co-owner their weighted part of a cancelled square', async () => {
+      const board = await createSharedBoard(gameId + 2, {});
+      const shares = sharesOf(board, squareIndex);
+
+      await program.methods
+        .cancelBoard()
+        .accounts({ board, canceller: authority.publicKey })
+        .signers([authority])
+        .rpc();
+
+      const claimRefund = (owner: Keypair) =>
+        program.methods
+          .claimRefund(squareIndex)
+          .accounts({
+            board,
+            owner: owner.publicKey,
+            ownerTokenAccount: null,
+            ownerShares: shares,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([owner])
+          .rpc();
+
+      const friendBefore = await provider.connection.getBalance(
+        friend.publicKey,
+      );
+      await claimRefund(friend);
+      const friendAfter = await provider.connection.getBalance(
+        friend.publicKey,
+      );
+      expect(friendAfter - friendBefore).to.be.approximately(
+        Math.floor(squarePrice / 3),
+        10000,
+      );
+
+      try {
+        await claimRefund(friend);
+
+        expect.fail('Should have thrown an error for a second refund');
+      } catch (error) {
+        expect(error.message).to.include('AlreadyRefunded');
+      }
+
+      let boardAccount = await program.account.board.fetch(board);
+      expect(boardAccount.refundedSquares.testn(squareIndex)).to.be.false;
+
+      const buyerBefore = await provider.connection.getBalance(
+        buyer.publicKey,
+      );
+      await claimRefund(buyer);
+      const buyerAfter = await provider.connection.getBalance(buyer.publicKey);
+      expect(buyerAfter - buyerBefore).to.be.approximately(
+        squarePrice - Math.floor(squarePrice / 3),
+        10000,
+      );
+
+      boardAccount = await program.account.board.fetch(board);
+      expect(boardAccount.refundedSquares.testn(squareIndex)).to.be.true;
+      expect(boardAccount.totalPot.toNumber()).to.equal(0);
+    });
+  });
+});
@@ -0,0 +1,160 @@
+// tests/signed-score.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import {
+  PublicKey,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, initializeOracleRegistry } from './helpers';
+
+describe('Signed Score Attestations', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const seed = randomBytes(32);
+  const feed = Keypair.generate();
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+
+  const attestation = (
+    quarter: number,
+    home: number,
+    away: number,
+    isFinal: boolean,
+    timestamp: number,
+  ) =>
+    Buffer.concat([
+      Buffer.from('squares:score_attestation'),
+      new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      Buffer.from([quarter, home, away, isFinal ? 1 : 0]),
+      new anchor.BN(timestamp).toArrayLike(Buffer, 'le', 8),
+    ]);
+
+  const relay = (
+    signer: Keypair,
+    quarter: number,
+    home: number,
+    away: number,
+    timestamp: number,
+  ) =>
+    program.methods
+      .recordSignedScore(home, away, quarter, false, new anchor.BN(timestamp))
+      .accounts({
+        board: boardPda,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message: attestation(quarter, home, away, false, timestamp),
+        }),
+      ])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 50000;
+
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        authority.publicKey,
+        2 * LAMPORTS_PER_SOL,
+      ),
+    );
+
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+    await program.methods.addScoreReporter(feed.publicKey).rpc();
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          unsoldWinnerPolicy: { refundProRata: {} },
+          salesCloseAt: new anchor.BN(now + 1800),
+          hostCanReport: false,
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Records a score relayed by an untrusted payer', async () => {
+    const now = Math.floor(Date.now() / 1000);
+    await relay(feed, 1, 7, 0, now);
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.periods[0].scored).to.be.true;
+    expect(boardAccount.periods[0].homeScore).to.equal(7);
+    expect(boardAccount.lastAttestationAt.toNumber()).to.equal(now);
+  });
+
+  it('Rejects a replayed attestation', async () => {
+    const boardAccount = await program.account.board.fetch(boardPda);
+    try {
+      await relay(feed, 1, 7, 0, boardAccount.lastAttestationAt.toNumber());
+
+      expect.fail('Should have thrown an error for a replay');
+    } catch (error) {
+      expect(error.message).to.include('AttestationReplayed');
+    }
+  });
+
+  it('Rejects an attestation from an unregistered key', async () => {
+    try {
+      await relay(Keypair.generate(), 2, 14, 0, Math.floor(Date.now() / 1000) + 1);
+
+      expect.fail('Should have thrown an error for an unknown feed');
+    } catch (error) {
+      expect(error.message).to.include('Unauthorized');
+    }
+  });
+});
@@ -0,0 +1,144 @@
+// tests/sport-rules.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, initializeOracleRegistry } from './helpers';
+
+describe('Sport Rules', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const seed = randomBytes(32);
+
+  let authority: Keypair;
+  let boardPda: PublicKey;
+
+  // A soccer board: two halves, no extra time, last-digit scoring
+  const createBoard = async (payoutSplitBps: number[]) => {
+    const gameId = Math.floor(Math.random() * 10000) + 110000;
+    const [pda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          payoutSplitBps,
+          unsoldWinnerPolicy: { refundProRata: {} },
+          salesCloseAt: new anchor.BN(now + 1800),
+          sportRules: {
+            periods: 2,
+            overtime: { notPlayed: {} },
+            scoringDigit: { last: {} },
+            maxPointsPerPeriod: 10,
+          },
+        }),
+      )
+      .accounts({
+        board: pda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    return pda;
+  };
+
+  const record = (
+    home: number,
+    away: number,
+    period: number,
+    isFinal: boolean,
+  ) =>
+    program.methods
+      .recordScore(home, away, period, isFinal)
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    await provider.connection.confirmTransaction(
+      await provider.connection.requestAirdrop(
+        authority.publicKey,
+        2 * LAMPORTS_PER_SOL,
+      ),
+    );
+
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+  });
+
+  it('Rejects payout splits for periods the sport does not play', async () => {
+    try {
+      await createBoard([5000, 0, 5000, 0]);
+
+      expect.fail('Should have thrown an error for a third-period split');
+    } catch (error) {
+      expect(error.message).to.include('InvalidPayoutSplit');
+    }
+  });
+
+  it('Ends a game without overtime at the last regulation period', async () => {
+    boardPda = await createBoard([5000, 5000, 0, 0]);
+    await program.methods
+      .closeSales()
+      .accounts({ board: boardPda, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board: boardPda, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    await record(1, 0, 1, false);
+
+    try {
+      await record(12, 0, 2, false);
+
+      expect.fail('Should have thrown an error over the points cap');
+    } catch (error) {
+      expect(error.message).to.include('ImplausibleScore');
+    }
+
+    try {
+      await record(1, 1, 2, false);
+
+      expect.fail('Should have thrown an error for a tie left open');
+    } catch (error) {
+      expect(error.message).to.include('FinalRequired');
+    }
+
+    await record(1, 1, 2, true);
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.status).to.deep.equal({ final: {} });
+    expect(boardAccount.periods[1].scored).to.be.true;
+  });
+});
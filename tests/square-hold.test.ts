@@ -0,0 +1,157 @@
+// tests/square-hold.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import { defaultBoardConfig, squareMint, squareTokenAccount } from './helpers';
+
+describe('Square Holds', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const squarePrice = 0.01 * LAMPORTS_PER_SOL;
+  const holdDeposit = 0.002 * LAMPORTS_PER_SOL;
+  const holdDurationSecs = 3;
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+  let holder: Keypair;
+  let rival: Keypair;
+
+  const holdPda = (squareIndex: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from('hold'), boardPda.toBuffer(), Buffer.from([squareIndex])],
+      program.programId,
+    )[0];
+
+  const hold = (squareIndex: number) =>
+    program.methods
+      .holdSquare(squareIndex)
+      .accounts({
+        board: boardPda,
+        hold: holdPda(squareIndex),
+        holder: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    holder = Keypair.generate();
+    rival = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 70000;
+
+    for (const kp of [authority, holder, rival]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(squarePrice),
+        { public: {} },
+        defaultBoardConfig({
+          holdDurationSecs: new anchor.BN(holdDurationSecs),
+          holdDeposit: new anchor.BN(holdDeposit),
+          expiredHoldPolicy: { forfeitToPot: {} },
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+  });
+
+  it('Keeps a held square away from other buyers', async () => {
+    await hold(5);
+
+    try {
+      await program.methods
+        .purchaseSquare(5)
+        .accounts({
+          board: boardPda,
+          buyer: rival.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([rival])
+        .rpc();
+
+      expect.fail('Should have thrown an error for a held square');
+    } catch (error) {
+      expect(error.message).to.include('SquareHeld');
+    }
+  });
+
+  it('Lets the holder complete the purchase', async () => {
+    await program.methods
+      .purchaseHeldSquare()
+      .accountsPartial({
+        board: boardPda,
+        hold: holdPda(5),
+        squareMint: squareMint(program.programId, boardPda, 5),
+        squareTokenAccount: squareTokenAccount(
+          program.programId,
+          boardPda,
+          holder.publicKey,
+          5,
+        ),
+        holder: holder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.squares[5].toString()).to.equal(
+      holder.publicKey.toString(),
+    );
+    expect(boardAccount.heldSquares.isZero()).to.be.true;
+    expect(boardAccount.tokenizedSquares.testn(5)).to.be.true;
+    expect(await provider.connection.getAccountInfo(holdPda(5))).to.be.null;
+  });
+
+  it('Lets anyone clear an expired hold and forfeits the deposit', async () => {
+    await hold(6);
+    await new Promise((resolve) =>
+      setTimeout(resolve, (holdDurationSecs + 2) * 1000),
+    );
+
+    await program.methods
+      .releaseHold()
+      .accounts({
+        board: boardPda,
+        hold: holdPda(6),
+        holder: holder.publicKey,
+        releaser: rival.publicKey,
+      })
+      .signers([rival])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.totalPot.toNumber()).to.equal(
+      squarePrice + holdDeposit,
+    );
+    expect(boardAccount.heldSquares.isZero()).to.be.true;
+  });
+});
@@ -0,0 +1,240 @@
+// tests/square-resale.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import {
+  createSquareTokenAccountIx,
+  defaultBoardConfig,
+  squareMint,
+  squareTokenAccount,
+  squareTokenAccounts,
+} from './helpers';
+
+describe('Square Resale', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  const ask = 0.05 * LAMPORTS_PER_SOL;
+  const resaleRoyaltyBps = 500;
+
+  let gameId: number;
+  let boardPda: PublicKey;
+  let authority: Keypair;
+  let seller: Keypair;
+  let buyer: Keypair;
+  let friend: Keypair;
+
+  const listingPda = (squareIndex: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from('listing'), boardPda.toBuffer(), Buffer.from([squareIndex])],
+      program.programId,
+    )[0];
+
+  const tokenAccount = (owner: PublicKey, squareIndex: number) =>
+    squareTokenAccount(program.programId, boardPda, owner, squareIndex);
+
+  const list = (squareIndex: number) =>
+    program.methods
+      .listSquare(squareIndex, new anchor.BN(ask))
+      .accounts({
+        board: boardPda,
+        listing: listingPda(squareIndex),
+        seller: seller.publicKey,
+        sellerTokenAccount: tokenAccount(seller.publicKey, squareIndex),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+
+  const fill = (squareIndex: number) =>
+    program.methods
+      .fillListing()
+      .accounts({
+        board: boardPda,
+        listing: listingPda(squareIndex),
+        seller: seller.publicKey,
+        host: authority.publicKey,
+        buyer: buyer.publicKey,
+        squareMint: squareMint(program.programId, boardPda, squareIndex),
+        sellerTokenAccount: tokenAccount(seller.publicKey, squareIndex),
+        buyerTokenAccount: tokenAccount(buyer.publicKey, squareIndex),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        createSquareTokenAccountIx(
+          program.programId,
+          boardPda,
+          buyer.publicKey,
+          buyer.publicKey,
+          squareIndex,
+        ),
+      ])
+      .signers([buyer])
+      .rpc();
+
+  before(async () => {
+    authority = Keypair.generate();
+    seller = Keypair.generate();
+    buyer = Keypair.generate();
+    friend = Keypair.generate();
+    gameId = Math.floor(Math.random() * 10000) + 80000;
+
+    for (const kp of [authority, seller, buyer, friend]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    [boardPda] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        { public: {} },
+        defaultBoardConfig({
+          resaleRoyaltyBps,
+        }),
+      )
+      .accounts({
+        board: boardPda,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    await program.methods
+      .purchaseSquares(Buffer.from([20, 21, 22]))
+      .accounts({
+        board: boardPda,
+        buyer: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .remainingAccounts(
+        squareTokenAccounts(program.programId, boardPda, seller.publicKey, [
+          20, 21, 22,
+        ]),
+      )
+      .signers([seller])
+      .rpc();
+  });
+
+  it('Gifts a square to another wallet', async () => {
+    await program.methods
+      .transferSquare(20, friend.publicKey)
+      .accounts({
+        board: boardPda,
+        owner: seller.publicKey,
+        squareMint: squareMint(program.programId, boardPda, 20),
+        ownerTokenAccount: tokenAccount(seller.publicKey, 20),
+        recipientTokenAccount: tokenAccount(friend.publicKey, 20),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        createSquareTokenAccountIx(
+          program.programId,
+          boardPda,
+          seller.publicKey,
+          friend.publicKey,
+          20,
+        ),
+      ])
+      .signers([seller])
+      .rpc();
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.squares[20].toString()).to.equal(
+      friend.publicKey.toString(),
+    );
+    const balance = await provider.connection.getTokenAccountBalance(
+      tokenAccount(friend.publicKey, 20),
+    );
+    expect(balance.value.amount).to.equal('1');
+  });
+
+  it('Sells a listed square and pays the host royalty', async () => {
+    await list(21);
+
+    const hostBefore = await provider.connection.getBalance(
+      authority.publicKey,
+    );
+    await fill(21);
+    const hostAfter = await provider.connection.getBalance(authority.publicKey);
+
+    const boardAccount = await program.account.board.fetch(boardPda);
+    expect(boardAccount.squares[21].toString()).to.equal(
+      buyer.publicKey.toString(),
+    );
+    const balance = await provider.connection.getTokenAccountBalance(
+      tokenAccount(buyer.publicKey, 21),
+    );
+    expect(balance.value.amount).to.equal('1');
+    expect(hostAfter - hostBefore).to.equal((ask * resaleRoyaltyBps) / 10000);
+    expect(await provider.connection.getAccountInfo(listingPda(21))).to.be
+      .null;
+  });
+
+  it('Lets the new owner clear a listing left behind by a transfer', async () => {
+    await list(22);
+    await program.methods
+      .transferSquare(22, friend.publicKey)
+      .accounts({
+        board: boardPda,
+        owner: seller.publicKey,
+        squareMint: squareMint(program.programId, boardPda, 22),
+        ownerTokenAccount: tokenAccount(seller.publicKey, 22),
+        recipientTokenAccount: tokenAccount(friend.publicKey, 22),
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .preInstructions([
+        createSquareTokenAccountIx(
+          program.programId,
+          boardPda,
+          seller.publicKey,
+          friend.publicKey,
+          22,
+        ),
+      ])
+      .signers([seller])
+      .rpc();
+
+    await program.methods
+      .cancelListing()
+      .accounts({
+        board: boardPda,
+        listing: listingPda(22),
+        seller: seller.publicKey,
+        canceller: friend.publicKey,
+        tokenAccount: tokenAccount(friend.publicKey, 22),
+      })
+      .signers([friend])
+      .rpc();
+
+    expect(await provider.connection.getAccountInfo(listingPda(22))).to.be
+      .null;
+  });
+
+  it('Rejects listing a square the seller does not own', async () => {
+    try {
+      await list(20);
+
+      expect.fail('Should have thrown an error for a square already given away');
+    } catch (error) {
+      expect(error.message).to.include('NotSquareOwner');
+    }
+  });
+});
@@ -0,0 +1,270 @@
+// tests/unsold-winner.test.ts
+import * as anchor from '@coral-xyz/anchor';
+import { Program } from '@coral-xyz/anchor';
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
+import { expect } from 'chai';
+import { Squares } from '../target/types/squares';
+import {
+  defaultBoardConfig,
+  initializeOracleRegistry,
+  squareTokenAccount,
+} from './helpers';
+
+describe('Unsold Winner Policies', () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+
+  const program = anchor.workspace.Squares as Program<Squares>;
+  const provider = anchor.getProvider();
+
+  // An odd price leaves dust when the pot is split between two owners
+  const squarePrice = 1_000_001;
+  const unsoldSquare = 0;
+
+  let authority: Keypair;
+  let alice: Keypair;
+  let bob: Keypair;
+  let nextGameId = Math.floor(Math.random() * 10000) + 110000;
+
+  // Creates a commit-reveal board where alice buys squares 10 and 11 and bob
+  // buys square 12, then closes sales and reveals the headers
+  const createBoard = async (
+    unsoldWinnerPolicy: object,
+    payoutSplitBps: number[],
+  ) => {
+    const gameId = nextGameId++;
+    const seed = randomBytes(32);
+    const [board] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('board'),
+        new anchor.BN(gameId).toArrayLike(Buffer, 'le', 8),
+      ],
+      program.programId,
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    await program.methods
+      .createBoard(
+        new anchor.BN(gameId),
+        new anchor.BN(squarePrice),
+        { public: {} },
+        defaultBoardConfig({
+          randomnessMode: {
+            commitReveal: {
+              seedCommitment: Array.from(
+                createHash('sha256').update(seed).digest(),
+              ),
+              revealDeadline: new anchor.BN(now + 3600),
+            },
+          },
+          payoutSplitBps,
+          unsoldWinnerPolicy,
+          salesCloseAt: new anchor.BN(now + 1800),
+        }),
+      )
+      .accounts({
+        board,
+        authority: authority.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([authority])
+      .rpc();
+
+    for (const [buyer, squareIndex] of [
+      [alice, 10],
+      [alice, 11],
+      [bob, 12],
+    ] as const) {
+      await program.methods
+        .purchaseSquare(squareIndex)
+        .accounts({
+          board,
+          buyer: buyer.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyer])
+        .rpc();
+    }
+
+    await program.methods
+      .closeSales()
+      .accounts({ board, closer: authority.publicKey })
+      .signers([authority])
+      .rpc();
+    await program.methods
+      .revealSeed(Array.from(seed))
+      .accounts({ board, authority: authority.publicKey })
+      .signers([authority])
+      .rpc();
+
+    return board;
+  };
+
+  // Records a score per period whose digits land on `winners[period]`, then
+  // settles every period in order
+  const playGame = async (board: PublicKey, winners: number[]) => {
+    const { homeHeaders, awayHeaders } = await program.account.board.fetch(
+      board,
+    );
+    let [home, away] = [0, 0];
+    const bump = (score: number, digit: number) =>
+      score + ((digit - (score % 10) + 10) % 10);
+
+    for (const [i, square] of winners.entries()) {
+      home = bump(home, homeHeaders[Math.floor(square / 10)]);
+      away = bump(away, awayHeaders[square % 10]);
+      await program.methods
+        .recordScore(home, away, i + 1, i === winners.length - 1)
+        .accounts({ board, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    }
+    for (let quarter = 1; quarter <= winners.length; quarter++) {
+      await program.methods
+        .settleWinner(quarter)
+        .accounts({ board, authority: authority.publicKey })
+        .signers([authority])
+        .rpc();
+    }
+  };
+
+  // Claims a period share and returns the lamports that left the board
+  const claimShare = async (
+    board: PublicKey,
+    quarter: number,
+    claimant: Keypair,
+    squareIndex: number,
+  ) => {
+    const before = await provider.connection.getBalance(board);
+    await program.methods
+      .claimPeriodShare(quarter, squareIndex)
+      .accounts({
+        board,
+        claimant: claimant.publicKey,
+        claimantTokenAccount: squareTokenAccount(
+          program.programId,
+          board,
+          claimant.publicKey,
+          squareIndex,
+        ),
+        claimantShares: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([claimant])
+      .rpc();
+    return before - (await provider.connection.getBalance(board));
+  };
+
+  before(async () => {
+    authority = Keypair.generate();
+    alice = Keypair.generate();
+    bob = Keypair.generate();
+
+    for (const kp of [authority, alice, bob]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          2 * LAMPORTS_PER_SOL,
+        ),
+      );
+    }
+
+    try {
+      await initializeOracleRegistry(program).rpc();
+    } catch (error) {
+      expect(error.message).to.include('already in use');
+    }
+  });
+
+  it('Rolls an unsold period forward into the next one', async () => {
+    const board = await createBoard(
+      { rollForward: {} },
+      [2500, 2500, 2500, 2500],
+    );
+    await playGame(board, [unsoldSquare, 10, 12, 11]);
+
+    const { periods, totalPot } = await program.account.board.fetch(board);
+    const share = Math.floor((totalPot.toNumber() * 2500) / 10000);
+    expect(periods[0].outcome).to.deep.equal({ rolledForward: {} });
+    expect(periods[0].payoutAmount.toNumber()).to.equal(0);
+    expect(periods[1].rolloverIn.toNumber()).to.equal(share);
+    expect(periods[1].outcome).to.deep.equal({ winner: {} });
+    expect(periods[1].winner.toString()).to.equal(alice.publicKey.toString());
+    expect(periods[1].payoutAmount.toNumber()).to.equal(2 * share);
+  });
+
+  it('Splits an unsold period between owners, dust to the first sold square', async () => {
+    const board = await createBoard(
+      { splitAmongOwners: {} },
+      [0, 0, 0, 10000],
+    );
+    await playGame(board, [unsoldSquare, unsoldSquare, unsoldSquare, 5]);
+
+    let boardAccount = await program.account.board.fetch(board);
+    const final = boardAccount.periods[3];
+    expect(final.outcome).to.deep.equal({ splitAmongOwners: {} });
+    // Two distinct owners share the pot
+    expect(final.claimants).to.equal(2);
+    const pot = 3 * squarePrice;
+    const perClaim = Math.floor(pot / 2);
+
+    // A wallet claims once, through its first square
+    try {
+      await claimShare(board, 4, alice, 11);
+
+      expect.fail('Should have thrown an error for a non-first square');
+    } catch (error) {
+      expect(error.message).to.include('InvalidSquareIndex');
+    }
+
+    // Square 10 is the lowest sold square, so alice also gets the dust
+    expect(await claimShare(board, 4, alice, 10)).to.equal(
+      perClaim + (pot % 2),
+    );
+
+    try {
+      await claimShare(board, 4, alice, 10);
+
+      expect.fail('Should have thrown an error for a second claim');
+    } catch (error) {
+      expect(error.message).to.include('NoPayout');
+    }
+
+    expect(await claimShare(board, 4, bob, 12)).to.equal(perClaim);
+
+    // Periods that paid nothing never needed a claim
+    boardAccount = await program.account.board.fetch(board);
+    expect(boardAccount.status).to.deep.equal({ paid: {} });
+  });
+
+  it('Refunds an unsold period to every sold square pro rata', async () => {
+    const board = await createBoard({ refundProRata: {} }, [0, 0, 0, 10000]);
+    await playGame(board, [unsoldSquare, unsoldSquare, unsoldSquare, 5]);
+
+    let boardAccount = await program.account.board.fetch(board);
+    expect(boardAccount.periods[3].outcome).to.deep.equal({
+      refundProRata: {},
+    });
+    expect(boardAccount.periods[3].claimants).to.equal(3);
+
+    // Every square, not every wallet, gets its purchase price back
+    expect(await claimShare(board, 4, alice, 10)).to.equal(squarePrice);
+    expect(await claimShare(board, 4, alice, 11)).to.equal(squarePrice);
+
+    try {
+      await claimShare(board, 4, alice, 11);
+
+      expect.fail('Should have thrown an error for a second claim');
+    } catch (error) {
+      expect(error.message).to.include('NoPayout');
+    }
+
+    boardAccount = await program.account.board.fetch(board);
+    expect(boardAccount.status).to.not.deep.equal({ paid: {} });
+
+    expect(await claimShare(board, 4, bob, 12)).to.equal(squarePrice);
+
+    boardAccount = await program.account.board.fetch(board);
+    expect(boardAccount.status).to.deep.equal({ paid: {} });
+  });
+});